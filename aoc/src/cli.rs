@@ -0,0 +1,156 @@
+use std::error::Error;
+
+pub const USAGE: &str = "Usage: aoc run [DAYS] [--part N] [--input PATH]
+
+DAYS is a single day (3), a list (1,3,5), a range (2-4) or all (default).
+PATH defaults to day{day}/input.txt, where {day} is the zero padded day.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub days: Vec<u8>,
+    pub parts: Vec<u8>,
+    pub input: String,
+}
+
+impl RunArgs {
+    pub fn input_path(&self, day: u8) -> String {
+        self.input.replace("{day}", &format!("{:02}", day))
+    }
+}
+
+pub fn parse_args<I>(args: I, available: u8) -> Result<Command, Box<dyn Error>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => {}
+        Some("help") | Some("--help") | Some("-h") | None => return Ok(Command::Help),
+        Some(x) => return Err(format!("Unknown command {}", x).into()),
+    }
+
+    let mut days: Option<Vec<u8>> = None;
+    let mut parts = vec![1, 2];
+    let mut input = "day{day}/input.txt".to_string();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part: u8 = args.next().ok_or("Missing value for --part")?.parse()?;
+                if !(1..=2).contains(&part) {
+                    return Err(format!("Invalid part {}", part).into());
+                }
+                parts = vec![part];
+            }
+            "--input" => {
+                input = args.next().ok_or("Missing value for --input")?;
+            }
+            x if days.is_none() => days = Some(parse_days(x, available)?),
+            x => return Err(format!("Unexpected argument {}", x).into()),
+        }
+    }
+
+    let days = match days {
+        Some(days) => days,
+        None => (1..=available).collect(),
+    };
+    if days.is_empty() {
+        return Err("No days to run".into());
+    }
+
+    if days.len() > 1 && !input.contains("{day}") {
+        return Err("--input must contain {day} when running more than one day".into());
+    }
+
+    Ok(Command::Run(RunArgs { days, parts, input }))
+}
+
+fn parse_days(value: &str, available: u8) -> Result<Vec<u8>, Box<dyn Error>> {
+    if value == "all" {
+        return Ok((1..=available).collect());
+    }
+
+    let mut days = vec![];
+    for token in value.split(',') {
+        match token.split_once('-') {
+            Some((start, end)) => {
+                let start: u8 = start.parse()?;
+                let end: u8 = end.parse()?;
+                if start > end {
+                    return Err(format!("Day range {} runs backwards", token).into());
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(token.parse()?),
+        }
+    }
+
+    for day in &days {
+        if *day == 0 || *day > available {
+            return Err(format!("Day {} is not solved yet", day).into());
+        }
+    }
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_run_single_day() -> Result<(), Box<dyn Error>> {
+        let command = parse_args(args(&["run", "3", "--part", "2", "--input", "in.txt"]), 5)?;
+        assert_eq!(
+            command,
+            Command::Run(RunArgs {
+                days: vec![3],
+                parts: vec![2],
+                input: "in.txt".to_string(),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_run_days() -> Result<(), Box<dyn Error>> {
+        let Command::Run(run) = parse_args(args(&["run", "4,1-2,2"]), 5)? else {
+            panic!("expected run command");
+        };
+        assert_eq!(run.days, [1, 2, 4]);
+        assert_eq!(run.parts, [1, 2]);
+        assert_eq!(run.input_path(4), "day04/input.txt");
+
+        let Command::Run(run) = parse_args(args(&["run"]), 5)? else {
+            panic!("expected run command");
+        };
+        assert_eq!(run.days, [1, 2, 3, 4, 5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse_args(args(&["run", "6"]), 5).is_err());
+        assert!(parse_args(args(&["run", "0"]), 5).is_err());
+        assert!(parse_args(args(&["run", "1", "--part", "3"]), 5).is_err());
+        assert!(parse_args(args(&["run", "1-2", "--input", "in.txt"]), 5).is_err());
+        assert!(parse_args(args(&["walk"]), 5).is_err());
+        assert!(parse_args(args(&["run", "5-3"]), 5).is_err());
+        assert!(parse_args(args(&["run", "1,4-2"]), 5).is_err());
+        assert!(parse_args(args(&["run", "all"]), 0).is_err());
+        assert!(parse_args(args(&["run"]), 0).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+mod cli;
+
+use cli::{Command, RunArgs};
+use common::{Answer, solve};
+use std::error::Error;
+
+type Solver = fn(&str, &[u8]) -> Result<Vec<Answer>, Box<dyn Error>>;
+
+const SOLVERS: [Solver; 5] = [
+    solve::<day01::Day01>,
+    solve::<day02::Day02>,
+    solve::<day03::Day03>,
+    solve::<day04::Day04>,
+    solve::<day05::Day05>,
+];
+
+fn main() -> Result<(), Box<dyn Error>> {
+    match cli::parse_args(std::env::args().skip(1), SOLVERS.len() as u8)? {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Run(args) => run(&args)?,
+    }
+
+    Ok(())
+}
+
+fn run(args: &RunArgs) -> Result<(), Box<dyn Error>> {
+    let mut rows: Vec<[String; 4]> = vec![];
+    let mut failures = 0;
+
+    for &day in &args.days {
+        let solver = SOLVERS[(day - 1) as usize];
+        let result = std::fs::read_to_string(args.input_path(day))
+            .map_err(|e| format!("{}: {}", args.input_path(day), e).into())
+            .and_then(|buffer| solver(&buffer, &args.parts));
+
+        match result {
+            Ok(answers) => {
+                for answer in answers {
+                    let value = answer.value.unwrap_or_else(|e| {
+                        failures += 1;
+                        format!("error: {}", e)
+                    });
+                    rows.push([
+                        day.to_string(),
+                        answer.part.to_string(),
+                        value,
+                        format!("{:.2?}", answer.elapsed),
+                    ]);
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                rows.push([
+                    day.to_string(),
+                    "-".to_string(),
+                    format!("error: {}", e),
+                    "-".to_string(),
+                ]);
+            }
+        }
+    }
+
+    print_table(["Day", "Part", "Answer", "Time"], &rows);
+
+    if failures > 0 {
+        return Err(format!("{} answer(s) failed", failures).into());
+    }
+    Ok(())
+}
+
+fn print_table(header: [&str; 4], rows: &[[String; 4]]) {
+    let mut widths = header.map(|x| x.len());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let format_row = |cells: [&str; 4]| {
+        cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(header));
+    println!("{}", widths.map(|x| "-".repeat(x)).join("-+-"));
+    for row in rows {
+        println!("{}", format_row(row.each_ref().map(|x| x.as_str())));
+    }
+}
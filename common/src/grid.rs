@@ -0,0 +1,198 @@
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+}
+
+const NEIGHBORS4: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+const NEIGHBORS8: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Dense, row major 2D grid. Points outside the grid are never yielded by
+/// the neighbour and region iterators, and `get` returns `None` for them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows, padding short rows with `fill` so the
+    /// grid is as wide as the longest row.
+    pub fn from_rows(rows: Vec<Vec<T>>, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = rows.iter().map(|x| x.len()).max().unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.extend(row);
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a character grid, converting each character with `parse`.
+    /// Columns count characters, not bytes, and short lines are padded with
+    /// `fill`.
+    pub fn parse<E, F>(text: &str, fill: T, mut parse: F) -> Result<Grid<T>, E>
+    where
+        T: Clone,
+        F: FnMut(Point, char) -> Result<T, E>,
+    {
+        let rows = text
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, token)| parse(Point::new(x as i32, y as i32), token))
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<T>>, E>>()?;
+        Ok(Grid::from_rows(rows, fill))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        (0..self.width as i32).contains(&point.x) && (0..self.height as i32).contains(&point.y)
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.index_of(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Every point in the grid with its cell, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width.max(1);
+        self.cells.iter().enumerate().map(move |(index, cell)| {
+            let point = Point::new((index % width) as i32, (index / width) as i32);
+            (point, cell)
+        })
+    }
+
+    /// The orthogonal neighbours of `point` that lie inside the grid.
+    pub fn neighbors4(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        self.offsets(*point, &NEIGHBORS4)
+    }
+
+    /// The orthogonal and diagonal neighbours of `point` that lie inside the
+    /// grid.
+    pub fn neighbors8(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        self.offsets(*point, &NEIGHBORS8)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells in the rectangle from `min` to `max` inclusive, clipped to
+    /// the grid, in reading order.
+    pub fn region(&self, min: Point, max: Point) -> impl Iterator<Item = (Point, &T)> {
+        let xs = min.x.max(0)..=max.x.min(self.width as i32 - 1);
+        let ys = min.y.max(0)..=max.y.min(self.height as i32 - 1);
+        ys.flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+            .map(|point| (point, &self[&point]))
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        point: Point,
+        offsets: &'static [(i32, i32)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .map(move |(dx, dy)| Point::new(point.x + dx, point.y + dy))
+            .filter(|x| self.contains(x))
+    }
+
+    fn index_of(&self, point: &Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+}
+
+impl<T> Index<&Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: &Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<&Point> for Grid<T> {
+    fn index_mut(&mut self, point: &Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl std::str::FromStr for Grid<char> {
+    type Err = std::convert::Infallible;
+
+    /// Parses a plain character grid, padding short lines with `.`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Grid::parse(value, '.', |_, token| Ok(token))
+    }
+}
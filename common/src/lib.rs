@@ -0,0 +1,417 @@
+pub mod grid;
+#[cfg(any(test, feature = "test-support"))]
+pub mod rng;
+
+use std::error::Error;
+use std::io::Read;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// A single day's puzzle, parsed once from the raw input and then solved
+/// for each part.
+pub trait Solution {
+    type Input: FromStr<Err: Into<Box<dyn Error>>>;
+    type Output: std::fmt::Display;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>>;
+
+    fn parse(buffer: &str) -> Result<Self::Input, Box<dyn Error>> {
+        buffer.parse().map_err(Into::into)
+    }
+}
+
+/// Reads the whole puzzle input from stdin.
+pub fn read_stdin() -> Result<String, Box<dyn Error>> {
+    let mut buffer = String::new();
+
+    std::io::stdin().read_to_string(&mut buffer)?;
+
+    Ok(buffer)
+}
+
+/// Reads the puzzle input from stdin and prints the answer to both parts.
+pub fn run<S: Solution>() -> Result<(), Box<dyn Error>> {
+    let buffer = read_stdin()?;
+
+    let input = S::parse(&buffer)?;
+
+    let part1 = S::part1(&input)?;
+    println!("Part 1: {}", part1);
+
+    let part2 = S::part2(&input)?;
+    println!("Part 2: {}", part2);
+
+    Ok(())
+}
+
+/// Error for `--emit` when a binary was built without the `serde` feature.
+pub const EMIT_UNAVAILABLE: &str = "--emit needs a build with the serde feature (--features serde)";
+
+/// Formats the parsed input can be dumped in with `--emit`.
+#[derive(Debug, PartialEq)]
+pub enum Emit {
+    Json,
+}
+
+/// Parses the only option the generic runners accept: `--emit FORMAT`.
+pub fn parse_emit<I>(args: I) -> Result<Option<Emit>, Box<dyn Error>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None => Ok(None),
+        Some("--emit") => {
+            let format = match args.next().as_deref() {
+                Some("json") => Emit::Json,
+                Some(x) => return Err(format!("Unknown emit format {}", x).into()),
+                None => return Err("Missing value for --emit".into()),
+            };
+            match args.next() {
+                Some(x) => Err(format!("Unexpected argument {}", x).into()),
+                None => Ok(Some(format)),
+            }
+        }
+        Some(x) => Err(format!("Unexpected argument {}", x).into()),
+    }
+}
+
+/// Prints `value` to stdout as pretty printed JSON.
+#[cfg(feature = "serde")]
+pub fn print_json<T: serde::Serialize>(value: &T) -> Result<(), Box<dyn Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// The answer to one part of a puzzle, or why solving it failed, along
+/// with how long it took.
+#[derive(Debug)]
+pub struct Answer {
+    pub part: u8,
+    pub value: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+/// Parses `buffer` once and solves each of the requested `parts`. A part
+/// that fails is recorded in its `Answer` rather than stopping the others.
+pub fn solve<S: Solution>(buffer: &str, parts: &[u8]) -> Result<Vec<Answer>, Box<dyn Error>> {
+    let input = S::parse(buffer)?;
+
+    let mut answers = vec![];
+    for &part in parts {
+        let start = Instant::now();
+        let value = match part {
+            1 => S::part1(&input),
+            2 => S::part2(&input),
+            x => return Err(format!("Unknown part {}", x).into()),
+        };
+        answers.push(Answer {
+            part,
+            value: value.map(|x| x.to_string()).map_err(|e| e.to_string()),
+            elapsed: start.elapsed(),
+        });
+    }
+    Ok(answers)
+}
+
+/// Position of a token within the puzzle input. Lines and columns are
+/// both 1-based, and columns count characters rather than bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Locates `token` on line number `line`. `token` must be a slice of
+    /// `text`, which itself starts at column 1 of that line.
+    pub fn of(line: usize, text: &str, token: &str) -> Location {
+        let offset = offset_of(text, token);
+        let column = text[..offset].chars().count() + 1;
+        Location { line, column }
+    }
+
+    /// Locates `token`, which must be a slice of the multi-line `text`.
+    pub fn find(text: &str, token: &str) -> Location {
+        let before = &text[..offset_of(text, token)];
+        let line = before.matches('\n').count() + 1;
+        let start = before.rfind('\n').map_or(0, |x| x + 1);
+        Location::of(line, &text[start..], token)
+    }
+}
+
+fn offset_of(text: &str, token: &str) -> usize {
+    (token.as_ptr() as usize)
+        .checked_sub(text.as_ptr() as usize)
+        .filter(|x| *x <= text.len())
+        .expect("token is not a slice of text")
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// A token in the puzzle input that failed to parse. `kind` says what was
+/// wrong with it and displays as the text that goes before the token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError<K> {
+    pub location: Location,
+    pub token: String,
+    pub kind: K,
+}
+
+impl<K> ParseError<K> {
+    pub fn new(kind: K, location: Location, token: &str) -> ParseError<K> {
+        ParseError {
+            location,
+            token: token.to_string(),
+            kind,
+        }
+    }
+
+    pub fn location(&self) -> Location {
+        self.location
+    }
+}
+
+impl<K: std::fmt::Display> std::fmt::Display for ParseError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {} {:?}", self.location, self.kind, self.token)
+    }
+}
+
+impl<K: std::fmt::Debug + std::fmt::Display> Error for ParseError<K> {}
+
+/// Types parsed from a single line of input that report errors against
+/// their line number.
+pub trait ParseLine: Sized {
+    type Err;
+
+    fn parse_line(value: &str, line: usize) -> Result<Self, Self::Err>;
+}
+
+/// Puzzle input where every line parses into one `T`.
+#[derive(Debug)]
+pub struct Lines<T>(pub Vec<T>);
+
+impl<T: ParseLine> FromStr for Lines<T> {
+    type Err = T::Err;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Lines(
+            value
+                .lines()
+                .enumerate()
+                .map(|(index, x)| T::parse_line(x, index + 1))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+impl<T> Deref for Lines<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use grid::{Grid, Point};
+
+    impl ParseLine for u32 {
+        type Err = String;
+
+        fn parse_line(value: &str, line: usize) -> Result<Self, Self::Err> {
+            let token = value.trim_start();
+            token
+                .parse()
+                .map_err(|_| format!("{}: invalid number", Location::of(line, value, token)))
+        }
+    }
+
+    #[test]
+    fn test_lines() -> Result<(), Box<dyn Error>> {
+        let lines: Lines<u32> = "1\n2\n3\n".parse()?;
+        assert_eq!(*lines, [1, 2, 3]);
+
+        let error = "1\n  x\n3".parse::<Lines<u32>>().unwrap_err();
+        assert_eq!(error, "line 2, column 3: invalid number");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_location() {
+        let text = "Game 1: 3 blue";
+        assert_eq!(
+            Location::of(4, text, &text[8..9]),
+            Location { line: 4, column: 9 }
+        );
+        assert_eq!(
+            Location::of(4, text, &text[14..]),
+            Location {
+                line: 4,
+                column: 15
+            }
+        );
+
+        let text = "→.12";
+        assert_eq!(
+            Location::of(1, text, &text[4..]),
+            Location { line: 1, column: 3 }
+        );
+
+        let text = "seeds: 1\n\nx map:\n1 2 3";
+        assert_eq!(
+            Location::find(text, &text[0..5]),
+            Location { line: 1, column: 1 }
+        );
+        assert_eq!(
+            Location::find(text, &text[19..20]),
+            Location { line: 4, column: 3 }
+        );
+    }
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Lines<u32>;
+        type Output = u32;
+
+        fn part1(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+            input
+                .iter()
+                .try_fold(1u32, |acc, x| acc.checked_mul(*x))
+                .ok_or("product overflows".into())
+        }
+    }
+
+    #[test]
+    fn test_solve() -> Result<(), Box<dyn Error>> {
+        let values = |answers: &[Answer]| -> Vec<(u8, Result<String, String>)> {
+            answers.iter().map(|x| (x.part, x.value.clone())).collect()
+        };
+
+        let answers = solve::<Sum>("2\n3\n4", &[2, 1])?;
+        assert_eq!(
+            values(&answers),
+            [(2, Ok("24".to_string())), (1, Ok("9".to_string()))]
+        );
+
+        let answers = solve::<Sum>("70000\n70000", &[1, 2])?;
+        assert_eq!(
+            values(&answers),
+            [
+                (1, Ok("140000".to_string())),
+                (2, Err("product overflows".to_string()))
+            ]
+        );
+
+        assert!(solve::<Sum>("2", &[3]).is_err());
+        assert!(solve::<Sum>("x", &[1]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_emit() -> Result<(), Box<dyn Error>> {
+        let args = |values: &[&str]| values.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(parse_emit(args(&[]))?, None);
+        assert_eq!(parse_emit(args(&["--emit", "json"]))?, Some(Emit::Json));
+        assert!(parse_emit(args(&["--emit", "xml"])).is_err());
+        assert!(parse_emit(args(&["--emit"])).is_err());
+        assert!(parse_emit(args(&["--emit", "json", "x"])).is_err());
+        assert!(parse_emit(args(&["--part"])).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut rng = rng::Rng::new(7);
+        let values: Vec<u64> = (0..1000).map(|_| rng.range(3, 9)).collect();
+        assert!(values.iter().all(|x| (3..9).contains(x)));
+        for value in 3..9 {
+            assert!(values.contains(&value));
+        }
+
+        let mut other = rng::Rng::new(7);
+        assert_eq!(other.range(3, 9), values[0]);
+    }
+
+    #[test]
+    fn test_grid_parse() -> Result<(), Box<dyn Error>> {
+        let grid: Grid<char> = "ab\n→cd\ne".parse()?;
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(&Point::new(1, 1)), Some(&'c'));
+        assert_eq!(grid.get(&Point::new(2, 0)), Some(&'.'));
+        assert_eq!(grid.get(&Point::new(3, 0)), None);
+        assert_eq!(grid.get(&Point::new(0, -1)), None);
+
+        let rows: Vec<String> = grid.rows().map(|x| x.iter().collect()).collect();
+        assert_eq!(rows, ["ab.", "→cd", "e.."]);
+        let columns: Vec<String> = grid.columns().map(|x| x.collect()).collect();
+        assert_eq!(columns, ["a→e", "bc.", ".d."]);
+
+        let digits = Grid::parse("12\n3x", 0, |point, token| {
+            token
+                .to_digit(10)
+                .ok_or(format!("{:?} is not a digit", point))
+        });
+        assert_eq!(digits.unwrap_err(), "Point { x: 1, y: 1 } is not a digit");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid_neighbors() {
+        let grid = Grid::new(3, 2, 0);
+
+        let corner: Vec<Point> = grid.neighbors8(&Point::new(0, 0)).collect();
+        assert_eq!(
+            corner,
+            [Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]
+        );
+
+        let edge: Vec<Point> = grid.neighbors4(&Point::new(1, 1)).collect();
+        assert_eq!(edge, [Point::new(1, 0), Point::new(0, 1), Point::new(2, 1)]);
+
+        assert_eq!(grid.neighbors8(&Point::new(1, 0)).count(), 5);
+        assert_eq!(grid.neighbors4(&Point::new(5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn test_grid_region() -> Result<(), Box<dyn Error>> {
+        let mut grid: Grid<char> = "abc\ndef\nghi".parse()?;
+        grid[&Point::new(1, 1)] = 'E';
+
+        let region: String = grid
+            .region(Point::new(-1, 0), Point::new(1, 1))
+            .map(|(_, x)| *x)
+            .collect();
+        assert_eq!(region, "abdE");
+
+        let points: Vec<Point> = grid
+            .iter()
+            .map(|(point, _)| point)
+            .skip(4)
+            .take(2)
+            .collect();
+        assert_eq!(points, [Point::new(1, 1), Point::new(2, 1)]);
+
+        Ok(())
+    }
+}
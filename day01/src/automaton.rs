@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+
+/// Aho-Corasick automaton over a set of byte patterns, compiled into a full
+/// transition table so matching is a single lookup per input byte.
+///
+/// Each state only remembers its longest match: for a given end position the
+/// longest pattern is also the one that starts earliest, which is all the
+/// digit searches need.
+#[derive(Debug, Clone)]
+pub struct Automaton {
+    transitions: Vec<[u32; 256]>,
+    outputs: Vec<Option<(usize, u32)>>,
+    max_len: usize,
+}
+
+impl Automaton {
+    pub fn new<'a, I>(patterns: I) -> Automaton
+    where
+        I: IntoIterator<Item = (&'a [u8], u32)>,
+    {
+        let mut children: Vec<[Option<u32>; 256]> = vec![[None; 256]];
+        let mut outputs: Vec<Option<(usize, u32)>> = vec![None];
+        let mut max_len = 0;
+
+        for (pattern, value) in patterns {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut state = 0;
+            for &byte in pattern {
+                state = match children[state][byte as usize] {
+                    Some(next) => next as usize,
+                    None => {
+                        children.push([None; 256]);
+                        outputs.push(None);
+                        let next = children.len() - 1;
+                        children[state][byte as usize] = Some(next as u32);
+                        next
+                    }
+                };
+            }
+            if outputs[state].is_none() {
+                outputs[state] = Some((pattern.len(), value));
+            }
+            max_len = max_len.max(pattern.len());
+        }
+
+        let mut transitions = vec![[0u32; 256]; children.len()];
+        let mut fail = vec![0usize; children.len()];
+        let mut queue = VecDeque::from([0usize]);
+
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                match children[state][byte] {
+                    Some(child) => {
+                        let child = child as usize;
+                        if state != 0 {
+                            fail[child] = transitions[fail[state]][byte] as usize;
+                            if outputs[child].is_none() {
+                                outputs[child] = outputs[fail[child]];
+                            }
+                        }
+                        transitions[state][byte] = child as u32;
+                        queue.push_back(child);
+                    }
+                    None if state != 0 => {
+                        transitions[state][byte] = transitions[fail[state]][byte];
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        Automaton {
+            transitions,
+            outputs,
+            max_len,
+        }
+    }
+
+    /// Returns the value of the match that starts earliest in `bytes`,
+    /// preferring the longest pattern when several start at the same place.
+    pub fn find_first<I>(&self, bytes: I) -> Option<u32>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let mut state = 0;
+        let mut best: Option<(usize, u32)> = None;
+
+        for (index, byte) in bytes.into_iter().enumerate() {
+            if let Some((start, _)) = best
+                && index >= start + self.max_len
+            {
+                break;
+            }
+            state = self.transitions[state][byte as usize] as usize;
+            if let Some((len, value)) = self.outputs[state] {
+                let start = index + 1 - len;
+                if best.is_none_or(|(best_start, _)| start <= best_start) {
+                    best = Some((start, value));
+                }
+            }
+        }
+
+        best.map(|(_, value)| value)
+    }
+
+    /// Returns the value of the first pattern to complete in `bytes`,
+    /// preferring the longest pattern when several complete together.
+    pub fn find_earliest_end<I>(&self, bytes: I) -> Option<u32>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let mut state = 0;
+        for byte in bytes {
+            state = self.transitions[state][byte as usize] as usize;
+            if let Some((_, value)) = self.outputs[state] {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,243 @@
+use crate::automaton::Automaton;
+use std::borrow::Cow;
+
+const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+pub const ENGLISH: [(&str, u32); 21] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("ten", 10),
+    ("eleven", 11),
+    ("twelve", 12),
+    ("thirteen", 13),
+    ("fourteen", 14),
+    ("fifteen", 15),
+    ("sixteen", 16),
+    ("seventeen", 17),
+    ("eighteen", 18),
+    ("nineteen", 19),
+    ("twenty", 20),
+];
+
+pub const GERMAN: [(&str, u32); 21] = [
+    ("null", 0),
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+    ("zehn", 10),
+    ("elf", 11),
+    ("zwölf", 12),
+    ("dreizehn", 13),
+    ("vierzehn", 14),
+    ("fünfzehn", 15),
+    ("sechzehn", 16),
+    ("siebzehn", 17),
+    ("achtzehn", 18),
+    ("neunzehn", 19),
+    ("zwanzig", 20),
+];
+
+pub const FRENCH: [(&str, u32); 21] = [
+    ("zéro", 0),
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+    ("dix", 10),
+    ("onze", 11),
+    ("douze", 12),
+    ("treize", 13),
+    ("quatorze", 14),
+    ("quinze", 15),
+    ("seize", 16),
+    ("dix-sept", 17),
+    ("dix-huit", 18),
+    ("dix-neuf", 19),
+    ("vingt", 20),
+];
+
+pub const SPANISH: [(&str, u32); 21] = [
+    ("cero", 0),
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+    ("diez", 10),
+    ("once", 11),
+    ("doce", 12),
+    ("trece", 13),
+    ("catorce", 14),
+    ("quince", 15),
+    ("dieciséis", 16),
+    ("diecisiete", 17),
+    ("dieciocho", 18),
+    ("diecinueve", 19),
+    ("veinte", 20),
+];
+
+/// The spelled out numbers that count as digits when calibrating, on top of
+/// the plain ASCII digits which are always recognised.
+///
+/// Words worth more than nine are ignored unless `above_nine` is enabled.
+#[derive(Debug, Clone)]
+pub struct DigitLexicon {
+    table: Vec<(String, u32)>,
+    words: Vec<(String, u32)>,
+    case_insensitive: bool,
+    above_nine: bool,
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl DigitLexicon {
+    pub fn new(table: &[(&str, u32)]) -> DigitLexicon {
+        let table: Vec<(String, u32)> = table
+            .iter()
+            .map(|(word, value)| (word.to_string(), *value))
+            .collect();
+        DigitLexicon {
+            words: table.clone(),
+            table,
+            case_insensitive: false,
+            above_nine: false,
+            forward: Automaton::new([]),
+            backward: Automaton::new([]),
+        }
+        .rebuild()
+    }
+
+    /// A lexicon without any words, so only ASCII digits are recognised.
+    pub fn digits_only() -> DigitLexicon {
+        DigitLexicon::new(&[])
+    }
+
+    pub fn english() -> DigitLexicon {
+        DigitLexicon::new(&ENGLISH)
+    }
+
+    pub fn german() -> DigitLexicon {
+        DigitLexicon::new(&GERMAN)
+    }
+
+    pub fn french() -> DigitLexicon {
+        DigitLexicon::new(&FRENCH)
+    }
+
+    pub fn spanish() -> DigitLexicon {
+        DigitLexicon::new(&SPANISH)
+    }
+
+    pub fn case_insensitive(mut self, enabled: bool) -> DigitLexicon {
+        self.case_insensitive = enabled;
+        self.rebuild()
+    }
+
+    pub fn above_nine(mut self, enabled: bool) -> DigitLexicon {
+        self.above_nine = enabled;
+        self.rebuild()
+    }
+
+    /// The words this lexicon matches, lowercased when case insensitive.
+    pub fn words(&self) -> &[(String, u32)] {
+        &self.words
+    }
+
+    /// Lowercases `line` when case insensitive, ready for `first_digit` and
+    /// `last_digit`.
+    pub fn fold_case<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        if self.case_insensitive {
+            Cow::Owned(line.to_lowercase())
+        } else {
+            Cow::Borrowed(line)
+        }
+    }
+
+    /// Returns the value of the digit or word that starts earliest in
+    /// `line`. When several words start there, the longest wins so
+    /// "fourteen" beats "four".
+    pub fn first_digit(&self, line: &str) -> Option<u32> {
+        self.forward.find_first(line.bytes())
+    }
+
+    /// Returns the value of the digit or word that starts last in `line`,
+    /// scanning from the right so overlaps such as "eightwo" end in two.
+    pub fn last_digit(&self, line: &str) -> Option<u32> {
+        self.backward.find_earliest_end(line.bytes().rev())
+    }
+
+    fn rebuild(mut self) -> DigitLexicon {
+        self.words = self
+            .table
+            .iter()
+            .filter(|(_, value)| self.above_nine || *value <= 9)
+            .map(|(word, value)| {
+                if self.case_insensitive {
+                    (word.to_lowercase(), *value)
+                } else {
+                    (word.clone(), *value)
+                }
+            })
+            .collect();
+
+        let patterns: Vec<(Vec<u8>, u32)> = DIGITS
+            .iter()
+            .map(|(digit, value)| (digit.as_bytes().to_vec(), *value))
+            .chain(
+                self.words
+                    .iter()
+                    .map(|(word, value)| (word.as_bytes().to_vec(), *value)),
+            )
+            .collect();
+        self.forward = Automaton::new(
+            patterns
+                .iter()
+                .map(|(pattern, value)| (pattern.as_slice(), *value)),
+        );
+
+        let reversed: Vec<(Vec<u8>, u32)> = patterns
+            .into_iter()
+            .map(|(pattern, value)| (pattern.into_iter().rev().collect(), value))
+            .collect();
+        self.backward = Automaton::new(
+            reversed
+                .iter()
+                .map(|(pattern, value)| (pattern.as_slice(), *value)),
+        );
+        self
+    }
+}
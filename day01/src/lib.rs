@@ -0,0 +1,264 @@
+pub mod automaton;
+pub mod lexicon;
+
+use common::Solution;
+use lexicon::DigitLexicon;
+use std::error::Error;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = String;
+    type Output = u32;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        get_part1(input, MissingDigits::Error)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        get_part2(input, MissingDigits::Error)
+    }
+}
+
+/// What to do with a line that has no digits to calibrate from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingDigits {
+    Error,
+    Skip,
+    Zero,
+}
+
+pub fn get_part1(buffer: &str, policy: MissingDigits) -> Result<u32, Box<dyn Error>> {
+    sum_calibration_values(buffer, &DigitLexicon::digits_only(), policy)
+}
+
+pub fn get_part2(buffer: &str, policy: MissingDigits) -> Result<u32, Box<dyn Error>> {
+    sum_calibration_values(buffer, &DigitLexicon::english(), policy)
+}
+
+pub fn sum_calibration_values(
+    buffer: &str,
+    lexicon: &DigitLexicon,
+    policy: MissingDigits,
+) -> Result<u32, Box<dyn Error>> {
+    Ok(calibration_values(buffer, lexicon, policy)?.iter().sum())
+}
+
+/// The calibration value of each line, with lines that have no digits
+/// handled by `policy`: `Skip` leaves them out and `Zero` keeps them as 0.
+pub fn calibration_values(
+    buffer: &str,
+    lexicon: &DigitLexicon,
+    policy: MissingDigits,
+) -> Result<Vec<u32>, Box<dyn Error>> {
+    let mut values = vec![];
+    for (index, line) in buffer.lines().enumerate() {
+        values.push(match (get_calibration_value(line, lexicon), policy) {
+            (Some(value), _) => value,
+            (None, MissingDigits::Error) => {
+                return Err(format!("line {}: no digits in {:?}", index + 1, line).into());
+            }
+            (None, MissingDigits::Skip) => continue,
+            (None, MissingDigits::Zero) => 0,
+        });
+    }
+    Ok(values)
+}
+
+/// Calibration values for one line of input, `None` when it has no digits.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LineReport<'a> {
+    pub line: &'a str,
+    pub part1: Option<u32>,
+    pub part2: Option<u32>,
+}
+
+pub fn report(buffer: &str) -> Vec<LineReport<'_>> {
+    let digits_only = DigitLexicon::digits_only();
+    let english = DigitLexicon::english();
+    buffer
+        .lines()
+        .map(|line| LineReport {
+            line,
+            part1: get_calibration_value(line, &digits_only),
+            part2: get_calibration_value(line, &english),
+        })
+        .collect()
+}
+
+/// Combines the first and last digit on `line` as `first * 10 + last`.
+/// Words worth more than nine contribute their whole value to that sum.
+fn get_calibration_value(line: &str, lexicon: &DigitLexicon) -> Option<u32> {
+    let line = lexicon.fold_case(line);
+    let first = lexicon.first_digit(&line)?;
+    let last = lexicon.last_digit(&line)?;
+    Some(first * 10 + last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rng::Rng;
+
+    #[test]
+    fn test_part1_example() -> Result<(), Box<dyn Error>> {
+        let example = ["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"].join("\n");
+        let result = get_part1(&example, MissingDigits::Error)?;
+        assert_eq!(result, 142);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_part2_example() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "two1nine",
+            "eightwothree",
+            "abcone2threexyz",
+            "xtwone3four",
+            "4nineeightseven2",
+            "zoneight234",
+            "7pqrstsixteen",
+        ]
+        .join("\n");
+        let result = get_part2(&example, MissingDigits::Error)?;
+        assert_eq!(result, 281);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_digits() -> Result<(), Box<dyn Error>> {
+        let example = ["1abc2", "", "seven", "treb7uchet"].join("\n");
+
+        let error = get_part1(&example, MissingDigits::Error).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: no digits in \"\"");
+
+        assert_eq!(get_part1(&example, MissingDigits::Skip)?, 89);
+        assert_eq!(get_part1(&example, MissingDigits::Zero)?, 89);
+        assert_eq!(get_part2(&example, MissingDigits::Skip)?, 166);
+
+        let digits_only = DigitLexicon::digits_only();
+        let skipped = calibration_values(&example, &digits_only, MissingDigits::Skip)?;
+        assert_eq!(skipped, [12, 77]);
+        let zeroed = calibration_values(&example, &digits_only, MissingDigits::Zero)?;
+        assert_eq!(zeroed, [12, 0, 0, 77]);
+        let english = DigitLexicon::english();
+        let zeroed = calibration_values(&example, &english, MissingDigits::Zero)?;
+        assert_eq!(zeroed, [12, 0, 77, 77]);
+
+        assert_eq!(
+            get_calibration_value("seven", &DigitLexicon::digits_only()),
+            None
+        );
+        assert_eq!(
+            get_calibration_value("seven", &DigitLexicon::english()),
+            Some(77)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexicons() {
+        let examples = [
+            (DigitLexicon::german(), "xzweiundvierzig", 24),
+            (DigitLexicon::french(), "deuxtroisquatre", 24),
+            (DigitLexicon::spanish(), "ocho1cinco", 85),
+            (DigitLexicon::new(&[("uno", 1), ("dos", 2)]), "dosxuno", 21),
+        ];
+        for (lexicon, line, expected) in examples {
+            assert_eq!(get_calibration_value(line, &lexicon), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_lexicon_options() {
+        let lexicon = DigitLexicon::english();
+        assert_eq!(get_calibration_value("OneTwo3", &lexicon), Some(33));
+        assert_eq!(get_calibration_value("eleven3four", &lexicon), Some(34));
+
+        let lexicon = DigitLexicon::english().case_insensitive(true);
+        assert_eq!(get_calibration_value("OneTwo3", &lexicon), Some(13));
+
+        let lexicon = DigitLexicon::english().above_nine(true);
+        assert_eq!(get_calibration_value("eleven3four", &lexicon), Some(114));
+        assert_eq!(get_calibration_value("fourteen", &lexicon), Some(154));
+
+        let lexicon = DigitLexicon::french().above_nine(true);
+        assert_eq!(get_calibration_value("dix-sept", &lexicon), Some(177));
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let lexicon = DigitLexicon::english();
+        assert_eq!(get_calibration_value("eightwo", &lexicon), Some(82));
+        assert_eq!(get_calibration_value("xoneightx", &lexicon), Some(18));
+        assert_eq!(get_calibration_value("twone", &lexicon), Some(21));
+        assert_eq!(get_calibration_value("sevenine", &lexicon), Some(79));
+        assert_eq!(get_calibration_value("nothing", &lexicon), None);
+
+        let lexicon = DigitLexicon::english().above_nine(true);
+        assert_eq!(get_calibration_value("xfourteen", &lexicon), Some(154));
+        assert_eq!(get_calibration_value("fourteenine", &lexicon), Some(149));
+    }
+
+    #[test]
+    fn test_matches_naive_scan() {
+        // Reference implementation: try every word at every position.
+        fn naive(line: &str, lexicon: &DigitLexicon) -> Option<u32> {
+            let digits: Vec<u32> = line
+                .char_indices()
+                .filter_map(|(index, token)| {
+                    let slice = &line[index..];
+                    token.to_digit(10).or_else(|| {
+                        lexicon
+                            .words()
+                            .iter()
+                            .filter(|(word, _)| slice.starts_with(word.as_str()))
+                            .max_by_key(|(word, _)| word.len())
+                            .map(|(_, value)| *value)
+                    })
+                })
+                .collect();
+            Some(digits.first()? * 10 + digits.last()?)
+        }
+
+        let alphabet: Vec<char> = "eightwonesvxfurtzé3".chars().collect();
+        let mut rng = Rng::new(42);
+        let lexicons = [
+            DigitLexicon::english(),
+            DigitLexicon::english().above_nine(true),
+            DigitLexicon::french().above_nine(true),
+        ];
+        for _ in 0..2000 {
+            let length = rng.range(1, 20);
+            let line: String = (0..length).map(|_| *rng.choose(&alphabet)).collect();
+            for lexicon in &lexicons {
+                assert_eq!(
+                    get_calibration_value(&line, lexicon),
+                    naive(&line, lexicon),
+                    "{}",
+                    line
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_report_json() -> Result<(), Box<dyn Error>> {
+        let example = ["1abc2", "seven"].join("\n");
+        let json = serde_json::to_value(report(&example))?;
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "line": "1abc2", "part1": 12, "part2": 12 },
+                { "line": "seven", "part1": null, "part2": 77 },
+            ])
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use common::{Location, ParseError};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How many cubes of each colour the bag holds. Colours missing from the
+/// bag have a limit of zero.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Bag {
+    pub limits: BTreeMap<String, u32>,
+}
+
+/// What was wrong with the token a bag configuration failed to parse at.
+#[derive(Debug, PartialEq)]
+pub enum BagErrorKind {
+    MissingCount,
+    InvalidNumber,
+    InvalidColor,
+}
+
+impl fmt::Display for BagErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagErrorKind::MissingCount => write!(f, "expected color=count in"),
+            BagErrorKind::InvalidNumber => write!(f, "invalid number"),
+            BagErrorKind::InvalidColor => write!(f, "invalid color"),
+        }
+    }
+}
+
+/// Why a bag configuration failed to parse.
+pub type BagError = ParseError<BagErrorKind>;
+
+impl Bag {
+    pub fn limit(&self, color: &str) -> u32 {
+        self.limits.get(color).copied().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limits: Vec<String> = self
+            .limits
+            .iter()
+            .map(|(color, limit)| format!("{}={}", color, limit))
+            .collect();
+        write!(f, "{}", limits.join(", "))
+    }
+}
+
+impl Default for Bag {
+    /// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+    fn default() -> Self {
+        let limits = [("red", 12), ("green", 13), ("blue", 14)]
+            .into_iter()
+            .map(|(color, limit)| (color.to_string(), limit))
+            .collect();
+        Bag { limits }
+    }
+}
+
+impl std::str::FromStr for Bag {
+    type Err = BagError;
+
+    /// Parses `color=count` entries separated by commas or newlines, as
+    /// given to `--bag` or read from a config file. Anything after `#` on a
+    /// line is a comment.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = |kind: BagErrorKind, token: &str| {
+            ParseError::new(kind, Location::find(value, token), token)
+        };
+
+        let mut limits = BTreeMap::new();
+        for line in value.lines() {
+            let line = line.split('#').next().unwrap_or(line);
+            for entry in line.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+                let (color, limit) = entry
+                    .split_once('=')
+                    .ok_or_else(|| error(BagErrorKind::MissingCount, entry))?;
+                let (color, limit) = (color.trim(), limit.trim());
+                if color.is_empty() || !color.chars().all(char::is_alphabetic) {
+                    return Err(error(BagErrorKind::InvalidColor, color));
+                }
+                let limit: u32 = limit
+                    .parse()
+                    .map_err(|_| error(BagErrorKind::InvalidNumber, limit))?;
+                limits.insert(color.to_string(), limit);
+            }
+        }
+
+        Ok(Bag { limits })
+    }
+}
@@ -1,59 +1,130 @@
-use std::error::Error;
+use common::{Location, ParseError, ParseLine};
+use std::collections::BTreeMap;
+use std::fmt;
 
-#[derive(Debug)]
+/// Number of cubes revealed for each colour in one handful.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameSet {
-    pub red: u32,
-    pub green: u32,
-    pub blue: u32,
+    pub cubes: BTreeMap<String, u32>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     pub id: u32,
     pub game_sets: Vec<GameSet>,
 }
 
-impl std::str::FromStr for GameSet {
-    type Err = Box<dyn Error>;
+/// What was wrong with the token a game failed to parse at.
+#[derive(Debug, PartialEq)]
+pub enum GameErrorKind {
+    MissingPrefix,
+    MissingSets,
+    InvalidNumber,
+    MissingColor,
+    InvalidColor,
+}
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let mut red = 0;
-        let mut green = 0;
-        let mut blue = 0;
-        for cubes in value.split(", ") {
-            let mut tokens = cubes.split(' ');
-            let value: u32 = tokens.next().ok_or("invalid cube value")?.parse()?;
-            let color = tokens.next().ok_or("missing cube color")?;
-            match color {
-                "red" => red = value,
-                "green" => green = value,
-                "blue" => blue = value,
-                x => return Err(format!("Unknown Color {}", x).into()),
-            };
+impl fmt::Display for GameErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameErrorKind::MissingPrefix => write!(f, "missing \"Game \" prefix in"),
+            GameErrorKind::MissingSets => write!(f, "missing set values after"),
+            GameErrorKind::InvalidNumber => write!(f, "invalid number"),
+            GameErrorKind::MissingColor => write!(f, "missing cube color after"),
+            GameErrorKind::InvalidColor => write!(f, "invalid color"),
         }
-        Ok(GameSet { red, green, blue })
     }
 }
 
-impl std::str::FromStr for Game {
-    type Err = Box<dyn Error>;
+/// Why a game failed to parse.
+pub type GameError = ParseError<GameErrorKind>;
+
+impl GameSet {
+    pub fn count(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for GameSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cubes: Vec<String> = self
+            .cubes
+            .iter()
+            .map(|(color, count)| format!("{} {}", count, color))
+            .collect();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let game_sets: Vec<String> = self.game_sets.iter().map(|x| x.to_string()).collect();
+        write!(f, "Game {}: {}", self.id, game_sets.join("; "))
+    }
+}
+
+/// Parses the game set `value`, which is a slice of `text` on line `line`.
+fn parse_game_set(value: &str, text: &str, line: usize) -> Result<GameSet, GameError> {
+    let error = |kind: GameErrorKind, token: &str| {
+        ParseError::new(kind, Location::of(line, text, token), token)
+    };
+
+    let mut game_set = GameSet::default();
+    for cubes in value.split(", ") {
+        let (count, color) = cubes
+            .split_once(' ')
+            .ok_or_else(|| error(GameErrorKind::MissingColor, cubes))?;
+        let count: u32 = count
+            .parse()
+            .map_err(|_| error(GameErrorKind::InvalidNumber, count))?;
+        if color.is_empty() || !color.chars().all(char::is_alphabetic) {
+            return Err(error(GameErrorKind::InvalidColor, color));
+        }
+        game_set.cubes.insert(color.to_string(), count);
+    }
+    Ok(game_set)
+}
+
+impl std::str::FromStr for GameSet {
+    type Err = GameError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let mut tokens = value.split(": ");
-        let id: u32 = tokens
-            .next()
-            .ok_or("Missing id")?
+        parse_game_set(value, value, 1)
+    }
+}
+
+impl ParseLine for Game {
+    type Err = GameError;
+
+    fn parse_line(value: &str, line: usize) -> Result<Self, Self::Err> {
+        let error = |kind: GameErrorKind, token: &str| {
+            ParseError::new(kind, Location::of(line, value, token), token)
+        };
+
+        let (id, sets) = value
+            .split_once(": ")
+            .ok_or_else(|| error(GameErrorKind::MissingSets, value))?;
+        let id: u32 = id
             .strip_prefix("Game ")
-            .ok_or("Missing Game prefix")?
-            .parse()?;
+            .ok_or_else(|| error(GameErrorKind::MissingPrefix, id))?
+            .parse()
+            .map_err(|_| error(GameErrorKind::InvalidNumber, &id[5..]))?;
 
         let mut game_sets: Vec<GameSet> = vec![];
-        let sets = tokens.next().ok_or("missing set values")?.split("; ");
-
-        for set in sets {
-            game_sets.push(set.parse()?);
+        for set in sets.split("; ") {
+            game_sets.push(parse_game_set(set, value, line)?);
         }
 
         Ok(Game { id, game_sets })
     }
 }
+
+impl std::str::FromStr for Game {
+    type Err = GameError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Game::parse_line(value, 1)
+    }
+}
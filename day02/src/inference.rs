@@ -0,0 +1,89 @@
+use crate::bag::Bag;
+use crate::game::{Game, GameSet};
+use crate::{min_constraints, valid_game_set};
+
+/// A colour that a game set drew more cubes of than the bag holds.
+#[derive(Debug, PartialEq)]
+pub struct Excess {
+    pub color: String,
+    pub count: u32,
+    pub limit: u32,
+}
+
+/// A game set that rules out a bag, along with every colour it exceeded.
+#[derive(Debug, PartialEq)]
+pub struct Violation<'a> {
+    pub index: usize,
+    pub game_set: &'a GameSet,
+    pub excess: Vec<Excess>,
+}
+
+/// A game that a bag cannot have produced, explained by the game sets that
+/// broke its limits.
+#[derive(Debug, PartialEq)]
+pub struct Rejection<'a> {
+    pub game: &'a Game,
+    pub violations: Vec<Violation<'a>>,
+}
+
+/// The outcome of checking every game against a single bag.
+#[derive(Debug, PartialEq)]
+pub struct BagReport<'a> {
+    pub allowed: Vec<&'a Game>,
+    pub rejected: Vec<Rejection<'a>>,
+}
+
+/// The smallest bag that allows every one of `games`.
+pub fn smallest_bag(games: &[Game]) -> Bag {
+    let mut bag = Bag {
+        limits: Default::default(),
+    };
+    for game in games {
+        for (color, count) in min_constraints(game).cubes {
+            let limit = bag.limits.entry(color).or_insert(0);
+            *limit = (*limit).max(count);
+        }
+    }
+    bag
+}
+
+/// Splits `games` into those `bag` allows and those it rules out.
+pub fn check_bag<'a>(games: &'a [Game], bag: &Bag) -> BagReport<'a> {
+    let mut allowed = vec![];
+    let mut rejected = vec![];
+
+    for game in games {
+        let violations: Vec<Violation> = game
+            .game_sets
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| !valid_game_set(x, bag))
+            .map(|(index, game_set)| Violation {
+                index,
+                game_set,
+                excess: excess(game_set, bag),
+            })
+            .collect();
+
+        if violations.is_empty() {
+            allowed.push(game);
+        } else {
+            rejected.push(Rejection { game, violations });
+        }
+    }
+
+    BagReport { allowed, rejected }
+}
+
+fn excess(game_set: &GameSet, bag: &Bag) -> Vec<Excess> {
+    game_set
+        .cubes
+        .iter()
+        .filter(|(color, count)| **count > bag.limit(color))
+        .map(|(color, count)| Excess {
+            color: color.clone(),
+            count: *count,
+            limit: bag.limit(color),
+        })
+        .collect()
+}
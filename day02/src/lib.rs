@@ -0,0 +1,373 @@
+pub mod bag;
+pub mod game;
+pub mod inference;
+
+use bag::Bag;
+use common::{Lines, Solution};
+use game::{Game, GameSet};
+use std::collections::BTreeSet;
+use std::error::Error;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Lines<Game>;
+    type Output = u64;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        Ok(u64::from(get_part1(input, &Bag::default())))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        Ok(get_part2(input, &Bag::default()))
+    }
+}
+
+pub fn get_part1(games: &[Game], bag: &Bag) -> u32 {
+    let mut result = 0;
+    for game in games {
+        if game.game_sets.iter().all(|x| valid_game_set(x, bag)) {
+            result += game.id;
+        }
+    }
+    result
+}
+
+pub fn get_part2(games: &[Game], bag: &Bag) -> u64 {
+    games
+        .iter()
+        .map(min_constraints)
+        .map(|x| power(&x, bag))
+        .sum()
+}
+
+/// A game along with whether `bag` allows it and its minimum cube set.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GameReport<'a> {
+    pub game: &'a Game,
+    pub possible: bool,
+    pub minimum: GameSet,
+    pub power: u64,
+}
+
+pub fn report<'a>(games: &'a [Game], bag: &Bag) -> Vec<GameReport<'a>> {
+    games
+        .iter()
+        .map(|game| {
+            let minimum = min_constraints(game);
+            GameReport {
+                game,
+                possible: game.game_sets.iter().all(|x| valid_game_set(x, bag)),
+                power: power(&minimum, bag),
+                minimum,
+            }
+        })
+        .collect()
+}
+
+fn valid_game_set(game_set: &GameSet, bag: &Bag) -> bool {
+    game_set
+        .cubes
+        .iter()
+        .all(|(color, count)| *count <= bag.limit(color))
+}
+
+fn min_constraints(game: &Game) -> GameSet {
+    let mut constraints = GameSet::default();
+    for game_set in &game.game_sets {
+        for (color, count) in &game_set.cubes {
+            let minimum = constraints.cubes.entry(color.clone()).or_insert(0);
+            *minimum = (*minimum).max(*count);
+        }
+    }
+    constraints
+}
+
+/// Multiplies the cube counts for every colour in the bag or the set, so a
+/// bag colour that never shows up makes the power zero.
+fn power(game_set: &GameSet, bag: &Bag) -> u64 {
+    let colors: BTreeSet<&String> = bag.limits.keys().chain(game_set.cubes.keys()).collect();
+    colors
+        .into_iter()
+        .map(|x| u64::from(game_set.count(x)))
+        .product()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bag::BagErrorKind;
+    use common::rng::Rng;
+    use common::{Location, ParseError, ParseLine};
+    use game::GameErrorKind;
+    use inference::{Excess, check_bag, smallest_bag};
+
+    #[test]
+    fn test_part1_example() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ];
+        let games: Vec<Game> = example
+            .iter()
+            .map(|x| x.parse())
+            .collect::<Result<Vec<Game>, _>>()?;
+
+        let result = get_part1(&games, &Bag::default());
+        assert_eq!(result, 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2_example() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ];
+        let games: Vec<Game> = example
+            .iter()
+            .map(|x| x.parse())
+            .collect::<Result<Vec<Game>, _>>()?;
+
+        let result = get_part2(&games, &Bag::default());
+        assert_eq!(result, 2286);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let location = |line, column| Location { line, column };
+
+        let error = "Gme 1: 3 blue".parse::<Game>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(GameErrorKind::MissingPrefix, location(1, 1), "Gme 1")
+        );
+
+        let error = "Game 1 3 blue".parse::<Game>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(GameErrorKind::MissingSets, location(1, 1), "Game 1 3 blue")
+        );
+
+        let error = "Game x: 3 blue".parse::<Game>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(GameErrorKind::InvalidNumber, location(1, 6), "x")
+        );
+
+        let error = Game::parse_line("Game 2: 3 blue; 4 red, 1 p1nk", 7).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(GameErrorKind::InvalidColor, location(7, 26), "p1nk")
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 7, column 26: invalid color \"p1nk\""
+        );
+
+        let error = "3 blue, 4".parse::<GameSet>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(GameErrorKind::MissingColor, location(1, 9), "4")
+        );
+    }
+
+    #[test]
+    fn test_custom_bag() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "Game 1: 3 blue, 4 red; 2 yellow",
+            "Game 2: 1 blue, 2 green; 5 yellow",
+            "Game 3: 8 green, 6 blue, 20 red",
+        ];
+        let games: Vec<Game> = example
+            .iter()
+            .map(|x| x.parse())
+            .collect::<Result<Vec<Game>, _>>()?;
+
+        assert_eq!(get_part1(&games, &Bag::default()), 0);
+
+        let bag: Bag = "red=20, green=8, blue=6, yellow=2".parse()?;
+        assert_eq!(get_part1(&games, &bag), 4);
+
+        let bag: Bag = "# what if\nred = 4\nblue = 3\nyellow = 5 # extra\n".parse()?;
+        assert_eq!(get_part1(&games, &bag), 1);
+
+        // 3 * 4 * 2 + 1 * 2 * 5 * 0 + 8 * 6 * 20 * 0
+        assert_eq!(get_part2(&games, &bag), 24);
+        assert_eq!(get_part2(&games, &Bag::default()), 960);
+
+        let game: Game = "Game 1: 20 red, 50 green, 60 blue, 70 pink, 80 grey, 90 white".parse()?;
+        assert_eq!(get_part2(&[game], &Bag::default()), 30_240_000_000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_bag_errors() {
+        let error = "red=12, green".parse::<Bag>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                BagErrorKind::MissingCount,
+                Location { line: 1, column: 9 },
+                "green"
+            )
+        );
+
+        let error = "red=12\nblue=x".parse::<Bag>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                BagErrorKind::InvalidNumber,
+                Location { line: 2, column: 6 },
+                "x"
+            )
+        );
+    }
+
+    #[test]
+    fn test_bag_inference() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ];
+        let games: Vec<Game> = example
+            .iter()
+            .map(|x| x.parse())
+            .collect::<Result<Vec<Game>, _>>()?;
+
+        let bag = smallest_bag(&games);
+        assert_eq!(bag.to_string(), "blue=15, green=13, red=20");
+        let report = check_bag(&games, &bag);
+        assert_eq!(report.allowed.len(), 5);
+        assert!(report.rejected.is_empty());
+
+        let report = check_bag(&games, &Bag::default());
+        let allowed: Vec<u32> = report.allowed.iter().map(|x| x.id).collect();
+        assert_eq!(allowed, [1, 2, 5]);
+
+        let rejected: Vec<u32> = report.rejected.iter().map(|x| x.game.id).collect();
+        assert_eq!(rejected, [3, 4]);
+
+        let violations = &report.rejected[0].violations;
+        assert_eq!(violations.len(), 1);
+        assert!(std::ptr::eq(
+            violations[0].game_set,
+            &report.rejected[0].game.game_sets[0]
+        ));
+        assert_eq!(
+            violations[0].excess,
+            [Excess {
+                color: "red".to_string(),
+                count: 20,
+                limit: 12
+            }]
+        );
+
+        assert_eq!(report.rejected[1].violations[0].index, 2);
+        let excess = &report.rejected[1].violations[0].excess;
+        assert_eq!(excess.len(), 2);
+        assert_eq!((excess[0].color.as_str(), excess[0].count), ("blue", 15));
+        assert_eq!((excess[1].color.as_str(), excess[1].count), ("red", 14));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_example() -> Result<(), Box<dyn Error>> {
+        let game: Game = "Game 3: 8 green, 6 blue, 20 red; 5 blue".parse()?;
+        assert_eq!(game.to_string(), "Game 3: 6 blue, 8 green, 20 red; 5 blue");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_round_trip() -> Result<(), Box<dyn Error>> {
+        let colors = ["red", "green", "blue", "yellow", "cyan"];
+        let mut rng = Rng::new(2);
+
+        for _ in 0..500 {
+            let game_sets: Vec<String> = (0..rng.range(1, 5))
+                .map(|_| {
+                    let cubes: Vec<String> = (0..rng.range(1, 4))
+                        .map(|_| format!("{} {}", rng.range(0, 30), rng.choose(&colors)))
+                        .collect();
+                    cubes.join(", ")
+                })
+                .collect();
+            let line = format!("Game {}: {}", rng.range(1, 1000), game_sets.join("; "));
+
+            let game: Game = line.parse()?;
+            let canonical = game.to_string();
+            let reparsed: Game = canonical.parse()?;
+            assert_eq!(reparsed, game, "{}", line);
+            assert_eq!(reparsed.to_string(), canonical);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_report_json() -> Result<(), Box<dyn Error>> {
+        let games = ["Game 1: 3 blue, 4 red; 1 red, 2 green", "Game 2: 20 red"]
+            .iter()
+            .enumerate()
+            .map(|(index, line)| Game::parse_line(line, index + 1))
+            .collect::<Result<Vec<Game>, _>>()?;
+        let json = serde_json::to_value(report(&games, &Bag::default()))?;
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {
+                    "game": {
+                        "id": 1,
+                        "game_sets": [
+                            { "cubes": { "blue": 3, "red": 4 } },
+                            { "cubes": { "green": 2, "red": 1 } },
+                        ],
+                    },
+                    "possible": true,
+                    "minimum": { "cubes": { "blue": 3, "green": 2, "red": 4 } },
+                    "power": 24,
+                },
+                {
+                    "game": { "id": 2, "game_sets": [{ "cubes": { "red": 20 } }] },
+                    "possible": false,
+                    "minimum": { "cubes": { "red": 20 } },
+                    "power": 0,
+                },
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_json_round_trip() -> Result<(), Box<dyn Error>> {
+        let games = ["Game 1: 3 blue, 4 red; 1 red, 2 green", "Game 7: 1 violet"]
+            .iter()
+            .enumerate()
+            .map(|(index, line)| Game::parse_line(line, index + 1))
+            .collect::<Result<Vec<Game>, _>>()?;
+        let json = serde_json::to_string(&games)?;
+        let imported: Vec<Game> = serde_json::from_str(&json)?;
+        assert_eq!(imported, games);
+
+        Ok(())
+    }
+}
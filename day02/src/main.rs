@@ -1,106 +1,101 @@
-mod game;
-
-use game::{Game, GameSet};
-use std::cmp;
+use common::{Emit, Lines, read_stdin};
+use day02::bag::Bag;
+use day02::game::Game;
+use day02::inference::{check_bag, smallest_bag};
+use day02::{get_part1, get_part2};
 use std::error::Error;
-use std::io::Read;
+
+const USAGE: &str = "Usage: day02 [--bag red=12,green=13,blue=14] [--bag-file PATH] \
+                     [--explain] [--emit json] < input.txt";
+
+struct Args {
+    bag: Bag,
+    explain: bool,
+    emit: Option<Emit>,
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut buffer = String::new();
+    let Args { bag, explain, emit } = parse_args(std::env::args().skip(1))?;
 
-    std::io::stdin().read_to_string(&mut buffer)?;
+    let games: Lines<Game> = read_stdin()?.parse()?;
 
-    let games: Vec<Game> = buffer
-        .lines()
-        .map(|x| x.parse())
-        .collect::<Result<Vec<Game>, _>>()?;
+    if emit.is_some() {
+        return emit_json(&games, &bag);
+    }
 
-    let part1 = get_part1(&games);
+    if explain {
+        print_explanation(&games, &bag);
+    }
+
+    let part1 = get_part1(&games, &bag);
     println!("Part 1: {}", part1);
 
-    let part2 = get_part2(&games);
+    let part2 = get_part2(&games, &bag);
     println!("Part 2: {}", part2);
 
     Ok(())
 }
 
-fn get_part1(games: &[Game]) -> u32 {
-    let mut result = 0;
-    for game in games {
-        if game.game_sets.iter().all(valid_game_set) {
-            result += game.id;
-        }
-    }
-    result
+#[cfg(feature = "serde")]
+fn emit_json(games: &[Game], bag: &Bag) -> Result<(), Box<dyn Error>> {
+    common::print_json(&day02::report(games, bag))
 }
 
-fn get_part2(games: &[Game]) -> u32 {
-    games.iter().map(min_constraints).map(|x| power(&x)).sum()
+#[cfg(not(feature = "serde"))]
+fn emit_json(_games: &[Game], _bag: &Bag) -> Result<(), Box<dyn Error>> {
+    Err(common::EMIT_UNAVAILABLE.into())
 }
 
-fn valid_game_set(game_set: &GameSet) -> bool {
-    game_set.red <= 12 && game_set.green <= 13 && game_set.blue <= 14
-}
-
-fn min_constraints(game: &Game) -> GameSet {
-    let mut constraints = GameSet {
-        red: 0,
-        green: 0,
-        blue: 0,
-    };
-    for game_set in &game.game_sets {
-        constraints.red = cmp::max(constraints.red, game_set.red);
-        constraints.green = cmp::max(constraints.green, game_set.green);
-        constraints.blue = cmp::max(constraints.blue, game_set.blue);
+fn print_explanation(games: &[Game], bag: &Bag) {
+    println!("Smallest bag: {}", smallest_bag(games));
+
+    let report = check_bag(games, bag);
+    let allowed: Vec<String> = report.allowed.iter().map(|x| x.id.to_string()).collect();
+    println!("Allowed by {}: {}", bag, allowed.join(", "));
+
+    for rejection in &report.rejected {
+        println!("Game {} rejected:", rejection.game.id);
+        for violation in &rejection.violations {
+            let excess: Vec<String> = violation
+                .excess
+                .iter()
+                .map(|x| format!("{} {} > {}", x.count, x.color, x.limit))
+                .collect();
+            println!("  set {}: {}", violation.index + 1, excess.join(", "));
+        }
     }
-    constraints
 }
 
-fn power(game_set: &GameSet) -> u32 {
-    game_set.red * game_set.green * game_set.blue
-}
+fn parse_args<I>(args: I) -> Result<Args, Box<dyn Error>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut parsed = Args {
+        bag: Bag::default(),
+        explain: false,
+        emit: None,
+    };
+    let mut args = args.into_iter();
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_part1_example() -> Result<(), Box<dyn Error>> {
-        let example = [
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
-        ];
-        let games: Vec<Game> = example
-            .iter()
-            .map(|x| x.parse())
-            .collect::<Result<Vec<Game>, _>>()?;
-
-        let result = get_part1(&games);
-        assert_eq!(result, 8);
-
-        Ok(())
+    while let Some(arg) = args.next() {
+        if arg == "--explain" {
+            parsed.explain = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}\n{}", arg, USAGE))?;
+        match arg.as_str() {
+            "--bag" => parsed.bag = value.parse()?,
+            "--bag-file" => {
+                parsed.bag = std::fs::read_to_string(&value)
+                    .map_err(|e| format!("{}: {}", value, e))?
+                    .parse()?
+            }
+            "--emit" => parsed.emit = common::parse_emit([arg, value])?,
+            x => return Err(format!("Unexpected argument {}\n{}", x, USAGE).into()),
+        };
     }
 
-    #[test]
-    fn test_part2_example() -> Result<(), Box<dyn Error>> {
-        let example = [
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
-        ];
-        let games: Vec<Game> = example
-            .iter()
-            .map(|x| x.parse())
-            .collect::<Result<Vec<Game>, _>>()?;
-
-        let result = get_part2(&games);
-        assert_eq!(result, 2286);
-
-        Ok(())
-    }
+    Ok(parsed)
 }
@@ -1,4 +0,0 @@
-#[derive(Debug)]
-struct EngineSchematic {
-
-}
@@ -0,0 +1,101 @@
+use crate::schematic::{Cell, Point, Schematic, Span};
+use std::collections::BTreeSet;
+
+/// A symbol in the schematic: which character it is and where.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Symbol {
+    pub point: Point,
+    pub kind: char,
+}
+
+/// A number along with every symbol touching it, in reading order.
+#[derive(Debug, PartialEq)]
+pub struct NumberAdjacency<'a> {
+    pub span: &'a Span,
+    pub symbols: Vec<Symbol>,
+}
+
+/// A symbol along with every number touching it, in reading order.
+#[derive(Debug, PartialEq)]
+pub struct SymbolAdjacency<'a> {
+    pub symbol: Symbol,
+    pub numbers: Vec<&'a Span>,
+}
+
+impl NumberAdjacency<'_> {
+    /// Whether any symbol of `kind` touches the number.
+    pub fn touches(&self, kind: char) -> bool {
+        self.symbols.iter().any(|x| x.kind == kind)
+    }
+
+    /// Whether the number touches at least one symbol and all of them are
+    /// of `kind`.
+    pub fn touches_only(&self, kind: char) -> bool {
+        !self.symbols.is_empty() && self.symbols.iter().all(|x| x.kind == kind)
+    }
+}
+
+impl SymbolAdjacency<'_> {
+    pub fn is(&self, kind: char) -> bool {
+        self.symbol.kind == kind
+    }
+
+    /// Whether exactly `count` distinct numbers touch the symbol.
+    pub fn has_number_count(&self, count: usize) -> bool {
+        self.numbers.len() == count
+    }
+
+    /// The product of the adjacent numbers, or `None` unless exactly
+    /// `count` of them touch the symbol.
+    pub fn ratio(&self, count: usize) -> Option<u64> {
+        if !self.has_number_count(count) {
+            return None;
+        }
+        Some(self.numbers.iter().map(|x| u64::from(x.value)).product())
+    }
+}
+
+impl Schematic {
+    /// The symbols touching `span`, in reading order.
+    pub fn adjacent_symbols(&self, span: &Span) -> Vec<Symbol> {
+        let min = Point::new(span.start.x - 1, span.start.y - 1);
+        let max = Point::new(span.end().x + 1, span.end().y + 1);
+        self.grid
+            .region(min, max)
+            .filter_map(|(point, cell)| match cell {
+                Cell::Symbol(kind) => Some(Symbol { point, kind: *kind }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The distinct numbers touching `point`, in reading order.
+    pub fn adjacent_numbers(&self, point: &Point) -> Vec<&Span> {
+        let indices: BTreeSet<usize> = self
+            .grid
+            .neighbors8(point)
+            .filter_map(|x| match self.grid[&x] {
+                Cell::Digit(_, index) => Some(index),
+                _ => None,
+            })
+            .collect();
+        indices.into_iter().map(|x| &self.numbers[x]).collect()
+    }
+
+    /// Every number with the symbols touching it, in reading order.
+    pub fn number_adjacency(&self) -> impl Iterator<Item = NumberAdjacency<'_>> {
+        self.numbers.iter().map(|span| NumberAdjacency {
+            span,
+            symbols: self.adjacent_symbols(span),
+        })
+    }
+
+    /// Every symbol with the numbers touching it, in reading order.
+    pub fn symbol_adjacency(&self) -> impl Iterator<Item = SymbolAdjacency<'_>> {
+        self.symbols().map(|(point, kind)| SymbolAdjacency {
+            symbol: Symbol { point, kind },
+            numbers: self.adjacent_numbers(&point),
+        })
+    }
+}
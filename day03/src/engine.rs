@@ -0,0 +1,95 @@
+use crate::adjacency::Symbol;
+use crate::schematic::{Cell, Point, Schematic, Span};
+use common::grid::Grid;
+use std::collections::VecDeque;
+
+/// A machine is a connected component of the schematic: numbers and
+/// symbols whose cells touch, directly or through each other, including
+/// diagonally.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Machine {
+    pub numbers: Vec<Span>,
+    pub symbols: Vec<Symbol>,
+    pub part_value: u32,
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Machine {
+    /// The smallest rectangle holding every cell of the machine, as its top
+    /// left and bottom right corners.
+    pub fn bounding_box(&self) -> (Point, Point) {
+        (self.min, self.max)
+    }
+}
+
+/// The machines of a schematic, ordered by their first cell in reading
+/// order.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EngineSchematic {
+    pub machines: Vec<Machine>,
+}
+
+impl EngineSchematic {
+    pub fn new(schematic: &Schematic) -> EngineSchematic {
+        let grid = &schematic.grid;
+        let mut labels: Grid<Option<usize>> = Grid::new(grid.width(), grid.height(), None);
+        let mut machines: Vec<Machine> = vec![];
+
+        for (point, cell) in grid.iter() {
+            if *cell == Cell::Empty || labels[&point].is_some() {
+                continue;
+            }
+
+            let label = machines.len();
+            let mut machine = Machine {
+                numbers: vec![],
+                symbols: vec![],
+                part_value: 0,
+                min: point,
+                max: point,
+            };
+            labels[&point] = Some(label);
+            let mut queue = VecDeque::from([point]);
+            while let Some(point) = queue.pop_front() {
+                machine.min = Point::new(machine.min.x.min(point.x), machine.min.y.min(point.y));
+                machine.max = Point::new(machine.max.x.max(point.x), machine.max.y.max(point.y));
+                for neighbor in grid.neighbors8(&point) {
+                    if grid[&neighbor] != Cell::Empty && labels[&neighbor].is_none() {
+                        labels[&neighbor] = Some(label);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            machines.push(machine);
+        }
+
+        for span in &schematic.numbers {
+            if let Some(label) = labels[&span.start] {
+                let machine = &mut machines[label];
+                machine.numbers.push(*span);
+                if schematic.is_part_number(span) {
+                    machine.part_value += span.value;
+                }
+            }
+        }
+        for (point, kind) in schematic.symbols() {
+            if let Some(label) = labels[&point] {
+                machines[label].symbols.push(Symbol { point, kind });
+            }
+        }
+
+        EngineSchematic { machines }
+    }
+
+    pub fn machines(&self) -> &[Machine] {
+        &self.machines
+    }
+
+    /// The sum of every machine's part value, which is the part 1 answer.
+    pub fn total_part_value(&self) -> u32 {
+        self.machines.iter().map(|x| x.part_value).sum()
+    }
+}
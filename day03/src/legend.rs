@@ -0,0 +1,117 @@
+use common::{Location, ParseError};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Which characters of a schematic are blank and which are symbols. Without
+/// an explicit symbol set, every character that is neither a digit nor the
+/// blank counts as a symbol.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Legend {
+    pub blank: char,
+    pub symbols: Option<BTreeSet<char>>,
+}
+
+/// What was wrong with the token a legend configuration failed to parse
+/// at.
+#[derive(Debug, PartialEq)]
+pub enum LegendErrorKind {
+    MissingValue,
+    UnknownKey,
+    InvalidBlank,
+    InvalidSymbol,
+}
+
+impl fmt::Display for LegendErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LegendErrorKind::MissingValue => write!(f, "expected key=value in"),
+            LegendErrorKind::UnknownKey => write!(f, "unknown key"),
+            LegendErrorKind::InvalidBlank => write!(f, "invalid blank"),
+            LegendErrorKind::InvalidSymbol => write!(f, "invalid symbol"),
+        }
+    }
+}
+
+/// Why a legend configuration failed to parse.
+pub type LegendError = ParseError<LegendErrorKind>;
+
+impl Legend {
+    pub fn is_symbol(&self, token: char) -> bool {
+        match &self.symbols {
+            Some(symbols) => symbols.contains(&token),
+            None => token != self.blank && !token.is_ascii_digit(),
+        }
+    }
+}
+
+impl Default for Legend {
+    /// The legend from the puzzle: `.` is blank and anything else that is
+    /// not a digit is a symbol.
+    fn default() -> Self {
+        Legend {
+            blank: '.',
+            symbols: None,
+        }
+    }
+}
+
+impl fmt::Display for Legend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "blank={}", self.blank)?;
+        if let Some(symbols) = &self.symbols {
+            write!(f, " symbols={}", symbols.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Legend {
+    type Err = LegendError;
+
+    /// Parses `blank=C` and `symbols=CHARS` entries separated by whitespace,
+    /// as given to `--legend` or read from a config file. Lines starting
+    /// with `#` are comments.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = |kind: LegendErrorKind, token: &str| {
+            ParseError::new(kind, Location::find(value, token), token)
+        };
+        let invalid = |token: char| token.is_ascii_digit() || token.is_whitespace();
+
+        let mut legend = Legend::default();
+        let mut symbols: Option<&str> = None;
+        for line in value.lines().filter(|x| !x.trim_start().starts_with('#')) {
+            for entry in line.split_whitespace() {
+                let (key, token) = entry
+                    .split_once('=')
+                    .ok_or_else(|| error(LegendErrorKind::MissingValue, entry))?;
+                match key {
+                    "blank" => {
+                        let mut chars = token.chars();
+                        legend.blank = match (chars.next(), chars.next()) {
+                            (Some(blank), None) if !invalid(blank) => blank,
+                            _ => return Err(error(LegendErrorKind::InvalidBlank, token)),
+                        };
+                    }
+                    "symbols" if token.is_empty() => {
+                        return Err(error(LegendErrorKind::MissingValue, entry));
+                    }
+                    "symbols" => symbols = Some(token),
+                    _ => return Err(error(LegendErrorKind::UnknownKey, key)),
+                }
+            }
+        }
+
+        if let Some(token) = symbols {
+            for (index, symbol) in token.char_indices() {
+                if invalid(symbol) || symbol == legend.blank {
+                    let token = &token[index..index + symbol.len_utf8()];
+                    return Err(error(LegendErrorKind::InvalidSymbol, token));
+                }
+            }
+            legend.symbols = Some(token.chars().collect());
+        }
+
+        Ok(legend)
+    }
+}
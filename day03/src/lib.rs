@@ -0,0 +1,742 @@
+pub mod adjacency;
+pub mod engine;
+pub mod legend;
+pub mod render;
+pub mod schematic;
+
+use adjacency::Symbol;
+use common::Solution;
+use schematic::{Schematic, Span};
+use std::error::Error;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Schematic;
+    type Output = u64;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        Ok(get_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        Ok(get_part2(input))
+    }
+}
+
+/// A number in the schematic, the symbols touching it and so whether it is
+/// a part number.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NumberReport {
+    pub span: Span,
+    pub symbols: Vec<Symbol>,
+    pub part_number: bool,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SchematicReport<'a> {
+    pub schematic: &'a Schematic,
+    pub numbers: Vec<NumberReport>,
+    pub gear_ratios: Vec<u64>,
+}
+
+pub fn report(schematic: &Schematic) -> SchematicReport<'_> {
+    let numbers: Vec<NumberReport> = schematic
+        .number_adjacency()
+        .map(|x| NumberReport {
+            span: *x.span,
+            part_number: !x.symbols.is_empty(),
+            symbols: x.symbols,
+        })
+        .collect();
+
+    let mut gear_ratios = schematic.get_gear_ratios();
+    gear_ratios.sort();
+
+    SchematicReport {
+        schematic,
+        numbers,
+        gear_ratios,
+    }
+}
+
+pub fn get_part1(schematic: &Schematic) -> u32 {
+    schematic
+        .numbers
+        .iter()
+        .filter(|x| schematic.is_part_number(x))
+        .map(|x| x.value)
+        .sum()
+}
+
+pub fn get_part2(schematic: &Schematic) -> u64 {
+    schematic.get_gear_ratios().iter().sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use adjacency::SymbolAdjacency;
+    use common::rng::Rng;
+    use common::{Location, ParseError};
+    use engine::EngineSchematic;
+    use legend::{Legend, LegendErrorKind};
+    use render::{RenderOptions, render};
+    use schematic::{Cell, Point, SchematicErrorKind};
+
+    #[test]
+    fn test_corner_case() -> Result<(), Box<dyn Error>> {
+        let example = ["12..+..", "..34...", "..@56..", "78.....", "......."].join("\n");
+
+        let schematic: Schematic = example.parse()?;
+        let result = get_part1(&schematic);
+        assert_eq!(result, 168);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_part_symbols() -> Result<(), Box<dyn Error>> {
+        let example = ["...", ".6.", "..."].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let result = get_part1(&schematic);
+        assert_eq!(result, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_symbols() -> Result<(), Box<dyn Error>> {
+        let example = ["fdwdawd", "f82+18o", "fhello+"].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let result = get_part1(&schematic);
+        assert_eq!(result, 100);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part1_example() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]
+        .join("\n");
+        let schematic: Schematic = example.parse()?;
+        let result = get_part1(&schematic);
+        assert_eq!(result, 4361);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2_example() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]
+        .join("\n");
+        let schematic: Schematic = example.parse()?;
+        let mut ratios = schematic.get_gear_ratios();
+        ratios.sort();
+        assert_eq!(ratios, [16345, 451490]);
+        let result = get_part2(&schematic);
+        assert_eq!(result, 467835);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gear_needs_exactly_two_numbers() -> Result<(), Box<dyn Error>> {
+        let example = ["1.2.", ".*..", "3.4.", "....", "5*6."].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let result = get_part2(&schematic);
+        assert_eq!(result, 30);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gear_ratio_beyond_u32() -> Result<(), Box<dyn Error>> {
+        let example = ["99999.", ".....*", "..99998"].join("\n");
+        let schematic: Schematic = example.parse()?;
+        assert_eq!(schematic.get_gear_ratios(), [9_999_700_002]);
+        assert_eq!(get_part2(&schematic), 9_999_700_002);
+
+        let summary = RenderOptions {
+            color: false,
+            summary: true,
+        };
+        let rendered = render(&schematic, summary);
+        assert!(
+            rendered.contains("gears 99999*99998=9999700002"),
+            "{}",
+            rendered
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let example = ["......", ".99999999999.", "..*..."].join("\n");
+        let error = example.parse::<Schematic>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                SchematicErrorKind::InvalidNumber,
+                Location { line: 2, column: 2 },
+                "99999999999"
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_schematic() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]
+        .join("\n");
+        let schematic: Schematic = example.parse()?;
+        let point = Point { x: 0, y: 1 };
+        assert_eq!(schematic.get_neighbors(&point), vec![]);
+        let point = Point { x: 0, y: 0 };
+        let neighbors = schematic.get_neighbors(&point);
+        assert_eq!(schematic.symbols().count(), 6);
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(
+            neighbors,
+            [
+                Point { x: -1, y: -1 },
+                Point { x: -1, y: 0 },
+                Point { x: -1, y: 1 },
+                Point { x: 0, y: -1 },
+                Point { x: 0, y: 1 },
+                Point { x: 1, y: -1 },
+                Point { x: 1, y: 1 },
+                Point { x: 2, y: -1 },
+                Point { x: 2, y: 1 },
+                Point { x: 3, y: -1 },
+                Point { x: 3, y: 0 },
+                Point { x: 3, y: 1 },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_schematic_grid() -> Result<(), Box<dyn Error>> {
+        let example = ["12.", "*..", "→34."].join("\n");
+        let schematic: Schematic = example.parse()?;
+        assert_eq!((schematic.grid.width(), schematic.grid.height()), (4, 3));
+        let numbers: Vec<(Point, u32)> = schematic
+            .numbers
+            .iter()
+            .map(|x| (x.start, x.value))
+            .collect();
+        assert_eq!(numbers, [(Point::new(0, 0), 12), (Point::new(1, 2), 34)]);
+        assert_eq!(
+            schematic.symbols().collect::<Vec<_>>(),
+            [(Point::new(0, 1), '*'), (Point::new(0, 2), '→')]
+        );
+        assert_eq!(schematic.grid[&Point::new(2, 2)], Cell::Digit('4', 1));
+        assert_eq!(
+            schematic.get_span(&Point::new(1, 2)).map(|x| x.value),
+            Some(34)
+        );
+        assert_eq!(schematic.get_span(&Point::new(2, 2)), None);
+        assert_eq!(schematic.get_gear_ratios(), [408]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_leading_zeros() -> Result<(), Box<dyn Error>> {
+        let example = ["007..", "...*.", "0.00."].join("\n");
+        let schematic: Schematic = example.parse()?;
+        assert_eq!(
+            schematic.numbers,
+            [
+                Span {
+                    start: Point::new(0, 0),
+                    len: 3,
+                    value: 7
+                },
+                Span {
+                    start: Point::new(0, 2),
+                    len: 1,
+                    value: 0
+                },
+                Span {
+                    start: Point::new(2, 2),
+                    len: 2,
+                    value: 0
+                },
+            ]
+        );
+        assert_eq!(schematic.text(&schematic.numbers[0]), "007");
+        assert_eq!(schematic.text(&schematic.numbers[2]), "00");
+        assert_eq!(schematic.numbers[0].end(), Point::new(2, 0));
+
+        let parts: Vec<bool> = schematic
+            .numbers
+            .iter()
+            .map(|x| schematic.is_part_number(x))
+            .collect();
+        assert_eq!(parts, [true, false, true]);
+        assert_eq!(schematic.to_string(), example);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_at_line_end() -> Result<(), Box<dyn Error>> {
+        let example = ["..0012#", "...0.", "....08$"].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let spans: Vec<(Point, Point, u32)> = schematic
+            .numbers
+            .iter()
+            .map(|x| (x.start, x.end(), x.value))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                (Point::new(2, 0), Point::new(5, 0), 12),
+                (Point::new(3, 1), Point::new(3, 1), 0),
+                (Point::new(4, 2), Point::new(5, 2), 8),
+            ]
+        );
+        assert_eq!(get_part1(&schematic), 20);
+
+        let ring = schematic.numbers[0].neighbors();
+        assert_eq!(ring.len(), 2 * 4 + 6);
+        assert!(ring.contains(&Point::new(6, 0)));
+        assert!(ring.contains(&Point::new(1, -1)));
+
+        // Numbers that run into the end of their line, including the last.
+        let example = ["..12", ".*..", "*..7"].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let spans: Vec<(Point, Point, u32)> = schematic
+            .numbers
+            .iter()
+            .map(|x| (x.start, x.end(), x.value))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                (Point::new(2, 0), Point::new(3, 0), 12),
+                (Point::new(3, 2), Point::new(3, 2), 7),
+            ]
+        );
+        assert_eq!(get_part1(&schematic), 12);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_positions() -> Result<(), Box<dyn Error>> {
+        // Each layout with its numbers in reading order and the part 1 sum.
+        let corpus: [(&str, &[u32], u32); 12] = [
+            ("7", &[7], 0),
+            ("*", &[], 0),
+            ("42", &[42], 0),
+            ("..123", &[123], 0),
+            ("..123\n....#", &[123], 123),
+            ("123..\n#....", &[123], 123),
+            (".#.\n..5", &[5], 5),
+            ("5..\n..#", &[5], 0),
+            ("12\n*.\n34\n", &[12, 34], 46),
+            (".*.\n9.8", &[9, 8], 17),
+            ("1*2\n...\n3.4", &[1, 2, 3, 4], 3),
+            ("→1\n*.", &[1], 1),
+        ];
+
+        for (example, numbers, total) in corpus {
+            let schematic: Schematic = example.parse()?;
+            let values: Vec<u32> = schematic.numbers.iter().map(|x| x.value).collect();
+            assert_eq!(values, numbers, "{:?}", example);
+            assert_eq!(get_part1(&schematic), total, "{:?}", example);
+            assert_eq!(schematic.to_string(), example.trim_end(), "{:?}", example);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multibyte_columns() -> Result<(), Box<dyn Error>> {
+        let example = ["€→12..", "....€.", "→5....", ".√...."].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let starts: Vec<Point> = schematic.numbers.iter().map(|x| x.start).collect();
+        assert_eq!(starts, [Point::new(2, 0), Point::new(1, 2)]);
+        assert_eq!(schematic.symbols().count(), 5);
+        assert_eq!(get_part1(&schematic), 17);
+        assert_eq!(schematic.to_string(), example);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_legend() -> Result<(), Box<dyn Error>> {
+        let legend: Legend = "# puzzle variant\nblank=_\nsymbols=*€".parse()?;
+        assert_eq!(legend.to_string(), "blank=_ symbols=*€");
+
+        let example = ["12_.3", "_*__€", "4.___"].join("\n");
+        let error = Schematic::parse(&example, &legend).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                SchematicErrorKind::InvalidSymbol,
+                Location { line: 1, column: 4 },
+                "."
+            )
+        );
+        assert_eq!(error.to_string(), "line 1, column 4: invalid symbol \".\"");
+
+        let example = example.replace('.', "_");
+        let schematic = Schematic::parse(&example, &legend)?;
+        assert_eq!(get_part1(&schematic), 19);
+        assert_eq!(schematic.to_string(), example);
+
+        let legend: Legend = "blank=.".parse()?;
+        let schematic = Schematic::parse("1_\n.#", &legend)?;
+        assert_eq!(schematic.symbols().count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_legend_errors() {
+        let error = |value: &str| value.parse::<Legend>().unwrap_err();
+        assert_eq!(
+            error("blank=. symbols"),
+            ParseError::new(
+                LegendErrorKind::MissingValue,
+                Location { line: 1, column: 9 },
+                "symbols"
+            )
+        );
+        assert_eq!(
+            error("blank=.\ncolor=red"),
+            ParseError::new(
+                LegendErrorKind::UnknownKey,
+                Location { line: 2, column: 1 },
+                "color"
+            )
+        );
+        assert_eq!(
+            error("blank=ab"),
+            ParseError::new(
+                LegendErrorKind::InvalidBlank,
+                Location { line: 1, column: 7 },
+                "ab"
+            )
+        );
+        assert_eq!(
+            error("symbols=*#.+"),
+            ParseError::new(
+                LegendErrorKind::InvalidSymbol,
+                Location {
+                    line: 1,
+                    column: 11
+                },
+                "."
+            )
+        );
+        assert_eq!(
+            error("symbols=*1"),
+            ParseError::new(
+                LegendErrorKind::InvalidSymbol,
+                Location {
+                    line: 1,
+                    column: 10
+                },
+                "1"
+            )
+        );
+    }
+
+    #[test]
+    fn test_adjacency_queries() -> Result<(), Box<dyn Error>> {
+        let example = ["12#..7", "..*...", "3..45$", "#....."].join("\n");
+        let schematic: Schematic = example.parse()?;
+
+        let symbols = schematic.adjacent_symbols(&schematic.numbers[0]);
+        assert_eq!(
+            symbols,
+            [
+                Symbol {
+                    point: Point::new(2, 0),
+                    kind: '#'
+                },
+                Symbol {
+                    point: Point::new(2, 1),
+                    kind: '*'
+                },
+            ]
+        );
+
+        let values = |spans: &[&Span]| spans.iter().map(|x| x.value).collect::<Vec<_>>();
+        assert_eq!(
+            values(&schematic.adjacent_numbers(&Point::new(2, 1))),
+            [12, 45]
+        );
+        assert_eq!(values(&schematic.adjacent_numbers(&Point::new(5, 2))), [45]);
+
+        let only_hash: Vec<u32> = schematic
+            .number_adjacency()
+            .filter(|x| x.touches_only('#'))
+            .map(|x| x.span.value)
+            .collect();
+        assert_eq!(only_hash, [3]);
+        let touching_star: Vec<u32> = schematic
+            .number_adjacency()
+            .filter(|x| x.touches('*'))
+            .map(|x| x.span.value)
+            .collect();
+        assert_eq!(touching_star, [12, 45]);
+
+        let lonely: Vec<char> = schematic
+            .symbol_adjacency()
+            .filter(|x| x.has_number_count(1))
+            .map(|x| x.symbol.kind)
+            .collect();
+        assert_eq!(lonely, ['#', '$', '#']);
+        let isolated = schematic
+            .symbol_adjacency()
+            .filter(|x| x.has_number_count(0))
+            .count();
+        let paired: Vec<char> = schematic
+            .symbol_adjacency()
+            .filter(|x| x.has_number_count(2))
+            .map(|x| x.symbol.kind)
+            .collect();
+        assert_eq!(paired, ['*']);
+        assert_eq!(
+            isolated + lonely.len() + paired.len(),
+            schematic.symbols().count()
+        );
+        let star: Vec<SymbolAdjacency> =
+            schematic.symbol_adjacency().filter(|x| x.is('*')).collect();
+        assert_eq!(star[0].ratio(2), Some(540));
+        assert_eq!(star[0].ratio(3), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_machines() -> Result<(), Box<dyn Error>> {
+        let example = ["12#..7", "...*..", "3..45$", "#....."].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let engine = EngineSchematic::new(&schematic);
+
+        let machines = engine.machines();
+        let values: Vec<Vec<u32>> = machines
+            .iter()
+            .map(|x| x.numbers.iter().map(|x| x.value).collect())
+            .collect();
+        assert_eq!(values, [vec![12, 45], vec![7], vec![3]]);
+        let kinds: Vec<String> = machines
+            .iter()
+            .map(|x| x.symbols.iter().map(|x| x.kind).collect())
+            .collect();
+        assert_eq!(kinds, ["#*$", "", "#"]);
+        let part_values: Vec<u32> = machines.iter().map(|x| x.part_value).collect();
+        assert_eq!(part_values, [57, 0, 3]);
+        let boxes: Vec<(Point, Point)> = machines.iter().map(|x| x.bounding_box()).collect();
+        assert_eq!(
+            boxes,
+            [
+                (Point::new(0, 0), Point::new(5, 2)),
+                (Point::new(5, 0), Point::new(5, 0)),
+                (Point::new(0, 2), Point::new(0, 3)),
+            ]
+        );
+        assert_eq!(engine.total_part_value(), get_part1(&schematic));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_total_part_value() -> Result<(), Box<dyn Error>> {
+        let cells = ['.', '.', '.', '.', '*', '#', '1', '2', '0'];
+        let mut rng = Rng::new(11);
+
+        for _ in 0..200 {
+            let width = rng.range(1, 10);
+            let lines: Vec<String> = (0..rng.range(1, 8))
+                .map(|_| (0..width).map(|_| *rng.choose(&cells)).collect())
+                .collect();
+            let example = lines.join("\n");
+
+            let schematic: Schematic = example.parse()?;
+            let engine = EngineSchematic::new(&schematic);
+            assert_eq!(
+                engine.total_part_value(),
+                get_part1(&schematic),
+                "{}",
+                example
+            );
+            let numbers: usize = engine.machines().iter().map(|x| x.numbers.len()).sum();
+            assert_eq!(numbers, schematic.numbers.len(), "{}", example);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() -> Result<(), Box<dyn Error>> {
+        let example = ["467..114..", "...*......", "..35..633.", "......#..."].join("\n");
+        let schematic: Schematic = example.parse()?;
+
+        let plain = RenderOptions {
+            color: false,
+            summary: false,
+        };
+        assert_eq!(render(&schematic, plain), example);
+
+        let summary = RenderOptions {
+            color: false,
+            summary: true,
+        };
+        assert_eq!(
+            render(&schematic, summary),
+            [
+                "467..114..  parts 467 (sum 467); others 114",
+                "...*......  gears 467*35=16345",
+                "..35..633.  parts 35 633 (sum 668)",
+                "......#...",
+            ]
+            .join("\n")
+        );
+
+        let color = RenderOptions {
+            color: true,
+            summary: false,
+        };
+        let first = render(&schematic, color);
+        let first = first.lines().next().unwrap_or_default();
+        assert!(first.starts_with("\x1b[32m4\x1b[0m\x1b[32m6\x1b[0m"));
+        assert!(first.contains("\x1b[31m1\x1b[0m"));
+        assert!(render(&schematic, color).contains("\x1b[1;33m*\x1b[0m"));
+        assert!(render(&schematic, color).contains("\x1b[36m#\x1b[0m"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_example() -> Result<(), Box<dyn Error>> {
+        let example = ["467..114..", "...*......", "..35..633.", "......#..."].join("\n");
+        let schematic: Schematic = example.parse()?;
+        assert_eq!(schematic.to_string(), example);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_round_trip() -> Result<(), Box<dyn Error>> {
+        let cells = ['.', '.', '.', '*', '#', '+', '1', '2', '5', '0'];
+        let mut rng = Rng::new(3);
+
+        for _ in 0..500 {
+            let width = rng.range(1, 12);
+            let lines: Vec<String> = (0..rng.range(1, 8))
+                .map(|_| {
+                    let mut digits = 0;
+                    (0..width)
+                        .map(|_| {
+                            let mut cell = *rng.choose(&cells);
+                            if cell.is_ascii_digit() && digits == 4 {
+                                cell = '.';
+                            }
+                            digits = if cell.is_ascii_digit() { digits + 1 } else { 0 };
+                            cell
+                        })
+                        .collect()
+                })
+                .collect();
+            let example = lines.join("\n");
+
+            let schematic: Schematic = example.parse()?;
+            let canonical = schematic.to_string();
+            let reparsed: Schematic = canonical.parse()?;
+            assert_eq!(reparsed, schematic, "{}", example);
+            assert_eq!(reparsed.to_string(), canonical);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_report_json() -> Result<(), Box<dyn Error>> {
+        let example = ["467.", "...*", "..35"].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let json = serde_json::to_value(report(&schematic))?;
+
+        let grid = &json["schematic"]["grid"];
+        assert_eq!(grid["width"], 4);
+        assert_eq!(grid["height"], 3);
+        assert_eq!(grid["cells"][0], serde_json::json!({ "Digit": ["4", 0] }));
+        assert_eq!(grid["cells"][3], serde_json::json!("Empty"));
+        assert_eq!(grid["cells"][7], serde_json::json!({ "Symbol": "*" }));
+        assert_eq!(json["schematic"]["blank"], ".");
+        assert_eq!(
+            json["numbers"],
+            serde_json::json!([
+                {
+                    "span": { "start": { "x": 0, "y": 0 }, "len": 3, "value": 467 },
+                    "symbols": [{ "point": { "x": 3, "y": 1 }, "kind": "*" }],
+                    "part_number": true,
+                },
+                {
+                    "span": { "start": { "x": 2, "y": 2 }, "len": 2, "value": 35 },
+                    "symbols": [{ "point": { "x": 3, "y": 1 }, "kind": "*" }],
+                    "part_number": true,
+                },
+            ])
+        );
+        assert_eq!(json["gear_ratios"], serde_json::json!([16345]));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_json_round_trip() -> Result<(), Box<dyn Error>> {
+        let example = ["467.", "...*", "..35"].join("\n");
+        let schematic: Schematic = example.parse()?;
+        let json = serde_json::to_string(&schematic.numbers)?;
+        let imported: Vec<Span> = serde_json::from_str(&json)?;
+        assert_eq!(imported, schematic.numbers);
+
+        Ok(())
+    }
+}
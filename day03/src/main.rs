@@ -1,129 +1,92 @@
-mod schematic;
-
-use schematic::Schematic;
+use common::{Emit, read_stdin};
+use day03::legend::Legend;
+use day03::render::{RenderOptions, render};
+use day03::schematic::Schematic;
+use day03::{get_part1, get_part2};
 use std::error::Error;
-use std::io::Read;
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let mut buffer = String::new();
+use std::io::IsTerminal;
 
-    std::io::stdin().read_to_string(&mut buffer)?;
-    let schematic: Schematic = buffer.parse()?;
+const USAGE: &str = "Usage: day03 [--legend \"blank=. symbols=*#\"] [--legend-file PATH] \
+                     [--render] [--summary] [--emit json] < input.txt";
 
-    let part1 = get_part1(&schematic);
-    println!("Part 1: {}", part1);
-
-    Ok(())
+struct Args {
+    legend: Legend,
+    render: bool,
+    summary: bool,
+    emit: Option<Emit>,
 }
 
-fn get_part1(schematic: &Schematic) -> u32 {
-    let mut total = 0;
-    for (point, number) in schematic.numbers.iter() {
-        if schematic.is_part_number(point) {
-            total += number;
-        }
-    }
-    total
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use schematic::Point;
-
-    #[test]
-    fn test_corner_case() -> Result<(), Box<dyn Error>> {
-        let example = ["12..+..", "..34...", "..@56..", "78.....", "......."].join("\n");
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = parse_args(std::env::args().skip(1))?;
 
-        let schematic: Schematic = example.parse()?;
-        let result = get_part1(&schematic);
-        assert_eq!(result, 168);
+    let schematic = Schematic::parse(&read_stdin()?, &args.legend)?;
 
-        Ok(())
+    if args.emit.is_some() {
+        return emit_json(&schematic);
     }
 
-    #[test]
-    fn test_no_part_symbols() -> Result<(), Box<dyn Error>> {
-        let example = ["...", ".6.", "..."].join("\n");
-        let schematic: Schematic = example.parse()?;
-        let result = get_part1(&schematic);
-        assert_eq!(result, 0);
-
-        Ok(())
+    if args.render || args.summary {
+        let options = RenderOptions {
+            color: std::io::stdout().is_terminal(),
+            summary: args.summary,
+        };
+        println!("{}", render(&schematic, options));
     }
 
-    #[test]
-    fn test_part_symbols() -> Result<(), Box<dyn Error>> {
-        let example = ["fdwdawd", "f82+18o", "fhello+"].join("\n");
-        let schematic: Schematic = example.parse()?;
-        let result = get_part1(&schematic);
-        assert_eq!(result, 100);
+    let part1 = get_part1(&schematic);
+    println!("Part 1: {}", part1);
 
-        Ok(())
-    }
+    let part2 = get_part2(&schematic);
+    println!("Part 2: {}", part2);
 
-    #[test]
-    fn test_part1_example() -> Result<(), Box<dyn Error>> {
-        let example = [
-            "467..114..",
-            "...*......",
-            "..35..633.",
-            "......#...",
-            "617*......",
-            ".....+.58.",
-            "..592.....",
-            "......755.",
-            "...$.*....",
-            ".664.598..",
-        ]
-        .join("\n");
-        let schematic: Schematic = example.parse()?;
-        let result = get_part1(&schematic);
-        assert_eq!(result, 4361);
+    Ok(())
+}
 
-        Ok(())
-    }
+#[cfg(feature = "serde")]
+fn emit_json(schematic: &Schematic) -> Result<(), Box<dyn Error>> {
+    common::print_json(&day03::report(schematic))
+}
 
-    #[test]
-    fn test_parse_schematic() -> Result<(), Box<dyn Error>> {
-        let example = [
-            "467..114..",
-            "...*......",
-            "..35..633.",
-            "......#...",
-            "617*......",
-            ".....+.58.",
-            "..592.....",
-            "......755.",
-            "...$.*....",
-            ".664.598..",
-        ]
-        .join("\n");
-        let schematic: Schematic = example.parse()?;
-        let point = Point { x: 0, y: 1 };
-        assert_eq!(schematic.get_neighbors(&point), vec![]);
-        let point = Point { x: 0, y: 0 };
-        let neighbors = schematic.get_neighbors(&point);
-        assert_eq!(schematic.symbols.len(), 6);
-        assert_eq!(schematic.numbers.len(), 10);
-        assert_eq!(
-            neighbors,
-            [
-                Point { x: -1, y: -1 },
-                Point { x: -1, y: 0 },
-                Point { x: -1, y: 1 },
-                Point { x: 0, y: -1 },
-                Point { x: 0, y: 1 },
-                Point { x: 1, y: -1 },
-                Point { x: 1, y: 1 },
-                Point { x: 2, y: -1 },
-                Point { x: 2, y: 1 },
-                Point { x: 3, y: -1 },
-                Point { x: 3, y: 0 },
-                Point { x: 3, y: 1 },
-            ]
-        );
+#[cfg(not(feature = "serde"))]
+fn emit_json(_schematic: &Schematic) -> Result<(), Box<dyn Error>> {
+    Err(common::EMIT_UNAVAILABLE.into())
+}
 
-        Ok(())
+fn parse_args<I>(args: I) -> Result<Args, Box<dyn Error>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut parsed = Args {
+        legend: Legend::default(),
+        render: false,
+        summary: false,
+        emit: None,
+    };
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--render" {
+            parsed.render = true;
+            continue;
+        }
+        if arg == "--summary" {
+            parsed.summary = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}\n{}", arg, USAGE))?;
+        match arg.as_str() {
+            "--legend" => parsed.legend = value.parse()?,
+            "--legend-file" => {
+                parsed.legend = std::fs::read_to_string(&value)
+                    .map_err(|e| format!("{}: {}", value, e))?
+                    .parse()?
+            }
+            "--emit" => parsed.emit = common::parse_emit([arg, value])?,
+            x => return Err(format!("Unexpected argument {}\n{}", x, USAGE).into()),
+        };
     }
+
+    Ok(parsed)
 }
@@ -0,0 +1,111 @@
+use crate::schematic::{Cell, Point, Schematic};
+use std::collections::HashMap;
+
+const RESET: &str = "\x1b[0m";
+const PART: &str = "\x1b[32m";
+const NOT_PART: &str = "\x1b[31m";
+const GEAR: &str = "\x1b[1;33m";
+const SYMBOL: &str = "\x1b[36m";
+const BLANK: &str = "\x1b[2m";
+
+/// How to draw a schematic in the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RenderOptions {
+    /// Colour part numbers green, other numbers red, gears yellow and
+    /// remaining symbols cyan.
+    pub color: bool,
+    /// Follow each row with its part numbers, other numbers and gears.
+    pub summary: bool,
+}
+
+/// Draws `schematic` back out as a grid, one line per row.
+pub fn render(schematic: &Schematic, options: RenderOptions) -> String {
+    let part_numbers: Vec<bool> = schematic
+        .numbers
+        .iter()
+        .map(|x| schematic.is_part_number(x))
+        .collect();
+    let gears: HashMap<Point, (u32, u32)> = schematic
+        .symbol_adjacency()
+        .filter(|x| x.is('*'))
+        .filter_map(|x| match x.numbers[..] {
+            [a, b] => Some((x.symbol.point, (a.value, b.value))),
+            _ => None,
+        })
+        .collect();
+
+    let mut lines = vec![];
+    for (y, row) in schematic.grid.rows().enumerate() {
+        let mut line = String::new();
+        for (x, cell) in row.iter().enumerate() {
+            let point = Point::new(x as i32, y as i32);
+            let (token, style) = match cell {
+                Cell::Empty => (schematic.blank, BLANK),
+                Cell::Digit(digit, index) if part_numbers[*index] => (*digit, PART),
+                Cell::Digit(digit, _) => (*digit, NOT_PART),
+                Cell::Symbol(symbol) if gears.contains_key(&point) => (*symbol, GEAR),
+                Cell::Symbol(symbol) => (*symbol, SYMBOL),
+            };
+            if options.color {
+                line.push_str(&format!("{}{}{}", style, token, RESET));
+            } else {
+                line.push(token);
+            }
+        }
+
+        if options.summary {
+            line.push_str(&summary(schematic, &part_numbers, &gears, y as i32));
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+fn summary(
+    schematic: &Schematic,
+    part_numbers: &[bool],
+    gears: &HashMap<Point, (u32, u32)>,
+    y: i32,
+) -> String {
+    let mut parts = vec![];
+    let mut others = vec![];
+    for (span, part) in schematic.numbers.iter().zip(part_numbers) {
+        if span.start.y == y {
+            if *part {
+                parts.push(span.value);
+            } else {
+                others.push(span.value);
+            }
+        }
+    }
+    let mut ratios: Vec<(i32, String)> = gears
+        .iter()
+        .filter(|(point, _)| point.y == y)
+        .map(|(point, (a, b))| {
+            let ratio = u64::from(*a) * u64::from(*b);
+            (point.x, format!("{}*{}={}", a, b, ratio))
+        })
+        .collect();
+    ratios.sort();
+
+    let join = |values: &[u32]| {
+        values
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut fields = vec![];
+    if !parts.is_empty() {
+        let sum: u32 = parts.iter().sum();
+        fields.push(format!("parts {} (sum {})", join(&parts), sum));
+    }
+    if !others.is_empty() {
+        fields.push(format!("others {}", join(&others)));
+    }
+    if !ratios.is_empty() {
+        let ratios: Vec<String> = ratios.into_iter().map(|(_, x)| x).collect();
+        fields.push(format!("gears {}", ratios.join(" ")));
+    }
+    format!("  {}", fields.join("; "))
+}
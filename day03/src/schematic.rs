@@ -1,88 +1,243 @@
-use std::collections::HashMap;
-use std::error::Error;
+use crate::legend::Legend;
+use common::grid::Grid;
+use common::{Location, ParseError};
+use std::fmt;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+pub use common::grid::Point;
+
+/// What occupies a single cell of the schematic. Digits remember which of
+/// the schematic's numbers they belong to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Cell {
+    Empty,
+    Symbol(char),
+    Digit(char, usize),
+}
+
+/// A number as it appears in the schematic: where its first digit is and
+/// how many digits it spans, which can be more than `value` needs when the
+/// number has leading zeros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    pub start: Point,
+    pub len: usize,
+    pub value: u32,
+}
+
+impl Span {
+    /// The point of the last digit.
+    pub fn end(&self) -> Point {
+        Point::new(self.start.x + self.len as i32 - 1, self.start.y)
+    }
+
+    /// The points of every digit, left to right.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let start = self.start;
+        (0..self.len as i32).map(move |x| Point::new(start.x + x, start.y))
+    }
+
+    /// The ring of points around the span, including any that fall outside
+    /// the schematic: the left column, then above and below each digit, then
+    /// the right column.
+    pub fn neighbors(&self) -> Vec<Point> {
+        let Point { x, y } = self.start;
+        let mut neighbors = vec![
+            Point { x: x - 1, y: y - 1 },
+            Point { x: x - 1, y },
+            Point { x: x - 1, y: y + 1 },
+        ];
+        for point in self.points() {
+            neighbors.push(Point { y: y - 1, ..point });
+            neighbors.push(Point { y: y + 1, ..point });
+        }
+        let x = self.end().x + 1;
+        neighbors.extend([Point { x, y: y - 1 }, Point { x, y }, Point { x, y: y + 1 }]);
+        neighbors
+    }
 }
 
-#[derive(Debug)]
+/// The engine schematic as a dense grid of cells, plus the span of every
+/// number it contains, in reading order. Columns count Unicode scalar
+/// values, so multibyte symbols take up a single cell.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Schematic {
-    pub numbers: HashMap<Point, u32>,
-    pub symbols: HashMap<Point, char>,
+    pub grid: Grid<Cell>,
+    pub numbers: Vec<Span>,
+    pub blank: char,
 }
 
+/// What was wrong with the token a schematic failed to parse at.
+#[derive(Debug, PartialEq)]
+pub enum SchematicErrorKind {
+    InvalidNumber,
+    InvalidSymbol,
+}
+
+impl fmt::Display for SchematicErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchematicErrorKind::InvalidNumber => write!(f, "invalid number"),
+            SchematicErrorKind::InvalidSymbol => write!(f, "invalid symbol"),
+        }
+    }
+}
+
+/// Why a schematic failed to parse.
+pub type SchematicError = ParseError<SchematicErrorKind>;
+
 impl Schematic {
-    pub fn is_part_number(&self, point: &Point) -> bool {
-        for neighbor in self.get_neighbors(point) {
-            if self.get_symbol(&neighbor).is_some() {
-                return true;
+    /// Parses a schematic whose blank and symbol characters are given by
+    /// `legend`. Characters that are neither digits, blank nor symbols are
+    /// rejected.
+    pub fn parse(value: &str, legend: &Legend) -> Result<Schematic, SchematicError> {
+        let error = |kind: SchematicErrorKind, line, text, token: &str| {
+            ParseError::new(kind, Location::of(line, text, token), token)
+        };
+
+        let mut rows: Vec<Vec<Cell>> = vec![];
+        let mut numbers: Vec<Span> = vec![];
+
+        for (y, line) in value.lines().enumerate() {
+            let mut row: Vec<Cell> = vec![];
+            for (column, token) in tokenize(line) {
+                if !token.starts_with(|x: char| x.is_ascii_digit()) {
+                    let symbol = token.chars().next().unwrap_or(legend.blank);
+                    row.push(match symbol {
+                        x if x == legend.blank => Cell::Empty,
+                        x if legend.is_symbol(x) => Cell::Symbol(x),
+                        _ => {
+                            return Err(error(
+                                SchematicErrorKind::InvalidSymbol,
+                                y + 1,
+                                line,
+                                token,
+                            ));
+                        }
+                    });
+                    continue;
+                }
+
+                let value: u32 = token
+                    .parse()
+                    .map_err(|_| error(SchematicErrorKind::InvalidNumber, y + 1, line, token))?;
+                row.extend(token.chars().map(|x| Cell::Digit(x, numbers.len())));
+                numbers.push(Span {
+                    start: Point::new(column as i32, y as i32),
+                    len: token.len(),
+                    value,
+                });
             }
+            rows.push(row);
         }
-        false
+
+        Ok(Schematic {
+            grid: Grid::from_rows(rows, Cell::Empty),
+            numbers,
+            blank: legend.blank,
+        })
     }
 
-    pub fn get_neighbors(&self, point: &Point) -> Vec<Point> {
-        let mut neighbors = vec![];
-        let x = point.x;
-        let y = point.y;
-        if let Some(number) = self.numbers.get(point) {
-            let length = number.to_string().len();
-            neighbors.extend([
-                Point { x: x - 1, y: y - 1 },
-                Point { x: x - 1, y },
-                Point { x: x - 1, y: y + 1 },
-            ]);
-            for index in 0..length {
-                let x = x + (index as i32);
-                neighbors.push(Point { x, y: y - 1 });
-                neighbors.push(Point { x, y: y + 1 });
+    pub fn is_part_number(&self, span: &Span) -> bool {
+        !self.adjacent_symbols(span).is_empty()
+    }
+
+    /// The span of the number starting at `point`, if there is one.
+    pub fn get_span(&self, point: &Point) -> Option<&Span> {
+        match self.grid.get(point) {
+            Some(Cell::Digit(_, index)) if self.numbers[*index].start == *point => {
+                Some(&self.numbers[*index])
             }
-            let x = x + (length as i32);
-            neighbors.extend([Point { x, y: y - 1 }, Point { x, y }, Point { x, y: y + 1 }])
+            _ => None,
         }
+    }
 
-        neighbors
+    /// The digits of `span` exactly as written in the source.
+    pub fn text(&self, span: &Span) -> String {
+        span.points()
+            .filter_map(|x| match self.grid.get(&x) {
+                Some(Cell::Digit(digit, _)) => Some(*digit),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The neighbours of the number starting at `point`, or none when no
+    /// number starts there.
+    pub fn get_neighbors(&self, point: &Point) -> Vec<Point> {
+        self.get_span(point)
+            .map(Span::neighbors)
+            .unwrap_or_default()
     }
 
     pub fn get_symbol(&self, point: &Point) -> Option<&char> {
-        self.symbols.get(point)
+        match self.grid.get(point) {
+            Some(Cell::Symbol(symbol)) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    /// Every symbol in the schematic, in reading order.
+    pub fn symbols(&self) -> impl Iterator<Item = (Point, char)> {
+        self.grid.iter().filter_map(|(point, cell)| match cell {
+            Cell::Symbol(symbol) => Some((point, *symbol)),
+            _ => None,
+        })
+    }
+
+    pub fn get_gear_ratios(&self) -> Vec<u64> {
+        self.symbol_adjacency()
+            .filter(|x| x.is('*'))
+            .filter_map(|x| x.ratio(2))
+            .collect()
+    }
+}
+
+impl fmt::Display for Schematic {
+    /// Writes the schematic back out as a grid, with its blank character for
+    /// empty cells.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines: Vec<String> = self
+            .grid
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Cell::Empty => self.blank,
+                        Cell::Symbol(symbol) => *symbol,
+                        Cell::Digit(digit, _) => *digit,
+                    })
+                    .collect()
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
     }
 }
 
 impl std::str::FromStr for Schematic {
-    type Err = Box<dyn Error>;
+    type Err = SchematicError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let mut numbers: HashMap<Point, u32> = HashMap::new();
-        let mut symbols: HashMap<Point, char> = HashMap::new();
+        Schematic::parse(value, &Legend::default())
+    }
+}
 
-        for (y, line) in value.lines().enumerate() {
-            let mut number_tokens: Vec<char> = vec![];
-            let mut point: Option<Point> = None;
-            for (x, token) in line.char_indices() {
-                let x = x as i32;
-                let y = y as i32;
-                if token.is_ascii_digit() {
-                    number_tokens.push(token);
-                    point.get_or_insert(Point { x, y });
-                } else {
-                    if let Some(key) = point {
-                        let number: u32 = number_tokens.iter().collect::<String>().parse()?;
-                        numbers.entry(key).or_insert(number);
-
-                        point = None;
-                        number_tokens.clear();
-                    }
-
-                    if token != '.' {
-                        let point = Point { x, y };
-                        symbols.entry(point).or_insert(token);
-                    }
-                }
+/// Splits a line into runs of ASCII digits and single other characters,
+/// each paired with the column it starts in. A run of digits always ends
+/// at the end of the line, so no number is left behind.
+fn tokenize(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut chars = line.char_indices().enumerate().peekable();
+    while let Some((column, (start, token))) = chars.next() {
+        let mut end = start + token.len_utf8();
+        if token.is_ascii_digit() {
+            while let Some((_, (index, _))) = chars.next_if(|(_, (_, x))| x.is_ascii_digit()) {
+                end = index + 1;
             }
         }
-        Ok(Schematic { numbers, symbols })
+        tokens.push((column, &line[start..end]));
     }
+    tokens
 }
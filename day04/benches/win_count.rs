@@ -0,0 +1,55 @@
+//! Compares counting wins with the compact `NumberSet` against the
+//! `HashSet` intersection cards used to be built on. Run with
+//! `cargo bench -p day04`.
+
+use common::rng::Rng;
+use day04::bitset::NumberSet;
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const CARDS: usize = 1_000_000;
+
+fn main() {
+    let mut rng = Rng::new(2023);
+    for limit in [100, 1000] {
+        let cards: Vec<(Vec<u32>, Vec<u32>)> = (0..CARDS)
+            .map(|_| {
+                let numbers = (0..10).map(|_| rng.range(0, limit) as u32).collect();
+                let winning = (0..25).map(|_| rng.range(0, limit) as u32).collect();
+                (numbers, winning)
+            })
+            .collect();
+
+        let hashed: Vec<(HashSet<u32>, HashSet<u32>)> = cards
+            .iter()
+            .map(|(a, b)| (a.iter().copied().collect(), b.iter().copied().collect()))
+            .collect();
+        let compact: Vec<(NumberSet, NumberSet)> = cards
+            .iter()
+            .map(|(a, b)| (a.clone().into(), b.clone().into()))
+            .collect();
+
+        let (hash_wins, hash_time) =
+            time(|| hashed.iter().map(|(a, b)| a.intersection(b).count()).sum());
+        let (compact_wins, compact_time) =
+            time(|| compact.iter().map(|(a, b)| a.intersection_count(b)).sum());
+        assert_eq!(hash_wins, compact_wins);
+
+        println!("{} cards with values below {}:", CARDS, limit);
+        println!("  HashSet   {:>10.2?}", hash_time);
+        println!("  NumberSet {:>10.2?}", compact_time);
+    }
+}
+
+/// Runs `f` a few times and keeps the fastest run.
+fn time<F: Fn() -> usize>(f: F) -> (usize, Duration) {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            let result = black_box(f());
+            (result, start.elapsed())
+        })
+        .min_by_key(|(_, elapsed)| *elapsed)
+        .unwrap_or_default()
+}
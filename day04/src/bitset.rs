@@ -0,0 +1,100 @@
+use std::cmp::Ordering;
+
+/// A set of card numbers. Sets whose values are all below 128 are kept as a
+/// single 128-bit mask so intersections are one `and` and a popcount;
+/// anything larger falls back to a sorted, deduplicated vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(into = "Vec<u32>"))]
+pub struct NumberSet(Repr);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Repr {
+    Bits(u128),
+    Sorted(Vec<u32>),
+}
+
+impl NumberSet {
+    pub fn contains(&self, value: u32) -> bool {
+        match &self.0 {
+            Repr::Bits(bits) => value < 128 && bits & (1 << value) != 0,
+            Repr::Sorted(values) => values.binary_search(&value).is_ok(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.0 {
+            Repr::Bits(bits) => bits.count_ones() as usize,
+            Repr::Sorted(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the set is held as a bitset rather than a sorted vector.
+    pub fn is_compact(&self) -> bool {
+        matches!(self.0, Repr::Bits(_))
+    }
+
+    /// The values in ascending order.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        match &self.0 {
+            Repr::Bits(bits) => {
+                let bits = *bits;
+                Box::new((0..128).filter(move |x| bits & (1 << x) != 0))
+            }
+            Repr::Sorted(values) => Box::new(values.iter().copied()),
+        }
+    }
+
+    /// How many values the two sets share.
+    pub fn intersection_count(&self, other: &NumberSet) -> usize {
+        match (&self.0, &other.0) {
+            (Repr::Bits(a), Repr::Bits(b)) => (a & b).count_ones() as usize,
+            (Repr::Bits(_), Repr::Sorted(values)) | (Repr::Sorted(values), Repr::Bits(_)) => {
+                let bits = if self.is_compact() { self } else { other };
+                values.iter().filter(|x| bits.contains(**x)).count()
+            }
+            (Repr::Sorted(a), Repr::Sorted(b)) => {
+                let (mut i, mut j, mut count) = (0, 0, 0);
+                while i < a.len() && j < b.len() {
+                    match a[i].cmp(&b[j]) {
+                        Ordering::Less => i += 1,
+                        Ordering::Greater => j += 1,
+                        Ordering::Equal => {
+                            count += 1;
+                            i += 1;
+                            j += 1;
+                        }
+                    }
+                }
+                count
+            }
+        }
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(values: I) -> Self {
+        let mut values: Vec<u32> = values.into_iter().collect();
+        if values.iter().all(|x| *x < 128) {
+            return NumberSet(Repr::Bits(values.iter().fold(0, |bits, x| bits | 1 << x)));
+        }
+        values.sort_unstable();
+        values.dedup();
+        NumberSet(Repr::Sorted(values))
+    }
+}
+
+impl From<Vec<u32>> for NumberSet {
+    fn from(values: Vec<u32>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl From<NumberSet> for Vec<u32> {
+    fn from(set: NumberSet) -> Self {
+        set.iter().collect()
+    }
+}
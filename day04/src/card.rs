@@ -1,16 +1,88 @@
-use std::collections::HashSet;
-use std::error::Error;
+use crate::bitset::NumberSet;
+use common::{Location, ParseError, ParseLine};
+use std::fmt;
 
-#[derive(Debug)]
+/// A scratchcard. The sets answer membership queries, while the raw lists
+/// keep the numbers exactly as written, duplicates and all.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "CardRecord")
+)]
 pub struct Card {
     pub id: u32,
-    pub numbers: HashSet<u32>,
-    pub winning_numbers: HashSet<u32>,
+    pub numbers: NumberSet,
+    pub winning_numbers: NumberSet,
+    pub raw_numbers: Vec<u32>,
+    pub raw_winning_numbers: Vec<u32>,
 }
 
+/// The fields a card is read back from. The sets are rebuilt from the raw
+/// lists, so an imported card can never disagree with itself.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CardRecord {
+    id: u32,
+    raw_numbers: Vec<u32>,
+    raw_winning_numbers: Vec<u32>,
+}
+
+#[cfg(feature = "serde")]
+impl From<CardRecord> for Card {
+    fn from(record: CardRecord) -> Self {
+        Card {
+            id: record.id,
+            numbers: record.raw_numbers.iter().copied().collect(),
+            winning_numbers: record.raw_winning_numbers.iter().copied().collect(),
+            raw_numbers: record.raw_numbers,
+            raw_winning_numbers: record.raw_winning_numbers,
+        }
+    }
+}
+
+/// What a strict parse holds every card to on top of the usual format:
+/// no number may repeat within a section, and each section must hold the
+/// given count of numbers when one is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CardRules {
+    pub numbers: Option<usize>,
+    pub winning_numbers: Option<usize>,
+}
+
+/// What was wrong with the token a card failed to parse at. The strict
+/// mode kinds carry the id of the card that broke the rule.
+#[derive(Debug, PartialEq)]
+pub enum CardErrorKind {
+    MissingPrefix,
+    MissingNumbers,
+    MissingWinningNumbers,
+    InvalidNumber,
+    DuplicateNumber(u32),
+    TooFewNumbers(u32),
+    TooManyNumbers(u32),
+}
+
+impl fmt::Display for CardErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardErrorKind::MissingPrefix => write!(f, "missing \"Card \" prefix in"),
+            CardErrorKind::MissingNumbers => write!(f, "missing numbers after"),
+            CardErrorKind::MissingWinningNumbers => write!(f, "missing winning numbers after"),
+            CardErrorKind::InvalidNumber => write!(f, "invalid number"),
+            CardErrorKind::DuplicateNumber(id) => write!(f, "card {} has duplicate number", id),
+            CardErrorKind::TooFewNumbers(id) => write!(f, "card {} has too few numbers in", id),
+            CardErrorKind::TooManyNumbers(id) => write!(f, "card {} has too many numbers from", id),
+        }
+    }
+}
+
+/// Why a card failed to parse.
+pub type CardError = ParseError<CardErrorKind>;
+
 impl Card {
     pub fn win_count(&self) -> usize {
-        self.numbers.intersection(&self.winning_numbers).count()
+        self.numbers.intersection_count(&self.winning_numbers)
     }
 
     pub fn get_points(&self) -> u32 {
@@ -21,46 +93,136 @@ impl Card {
             _ => base.pow(overlap - 1),
         }
     }
-}
 
-impl std::str::FromStr for Card {
-    type Err = Box<dyn Error>;
+    /// Parses a card like `parse_line`, then checks it against `rules`.
+    pub fn parse_strict(value: &str, line: usize, rules: &CardRules) -> Result<Card, CardError> {
+        let (id, numbers, winning_numbers) = split_card(value, line)?;
+        let card = Card::parse_body(id, numbers, winning_numbers, value, line)?;
+        let error = |kind: fn(u32) -> CardErrorKind, token: &str| {
+            ParseError::new(kind(id), Location::of(line, value, token), token)
+        };
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let mut tokens = value.split(": ");
-        let id = tokens
-            .next()
-            .ok_or("Missing card id")?
-            .strip_prefix("Card ")
-            .ok_or("Missing card prefix")?
-            .trim_start()
-            .parse()?;
-
-        tokens = tokens
-            .next()
-            .ok_or("Missing any number values")?
-            .split(" | ");
-
-        let numbers: HashSet<u32> = tokens
-            .next()
-            .ok_or("Missing numbers")?
-            .split(' ')
-            .filter(|x| !x.is_empty())
-            .map(|x| x.parse())
-            .collect::<Result<HashSet<u32>, _>>()?;
-
-        let winning_numbers: HashSet<u32> = tokens
-            .next()
-            .ok_or("Missing winning numbers")?
-            .split(' ')
-            .filter(|x| !x.is_empty())
-            .map(|x| x.parse())
-            .collect::<Result<HashSet<u32>, _>>()?;
+        let sections = [
+            (numbers, &card.raw_numbers, rules.numbers),
+            (
+                winning_numbers,
+                &card.raw_winning_numbers,
+                rules.winning_numbers,
+            ),
+        ];
+        for (section, values, count) in sections {
+            let tokens = tokens(section);
+            for (index, number) in values.iter().enumerate() {
+                if values[..index].contains(number) {
+                    return Err(error(CardErrorKind::DuplicateNumber, tokens[index]));
+                }
+            }
+            match count {
+                Some(count) if tokens.len() < count => {
+                    return Err(error(CardErrorKind::TooFewNumbers, section));
+                }
+                Some(count) if tokens.len() > count => {
+                    return Err(error(CardErrorKind::TooManyNumbers, tokens[count]));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(card)
+    }
+
+    /// Parses both number sections of the card with the given `id`.
+    fn parse_body(
+        id: u32,
+        numbers: &str,
+        winning_numbers: &str,
+        value: &str,
+        line: usize,
+    ) -> Result<Card, CardError> {
+        let parse_numbers = |numbers: &str| {
+            tokens(numbers)
+                .into_iter()
+                .map(|x| {
+                    x.parse().map_err(|_| {
+                        ParseError::new(
+                            CardErrorKind::InvalidNumber,
+                            Location::of(line, value, x),
+                            x,
+                        )
+                    })
+                })
+                .collect::<Result<Vec<u32>, _>>()
+        };
+        let raw_numbers = parse_numbers(numbers)?;
+        let raw_winning_numbers = parse_numbers(winning_numbers)?;
 
         Ok(Card {
             id,
-            numbers,
-            winning_numbers,
+            numbers: raw_numbers.iter().copied().collect(),
+            winning_numbers: raw_winning_numbers.iter().copied().collect(),
+            raw_numbers,
+            raw_winning_numbers,
         })
     }
 }
+
+/// Writes the numbers in their original order, right aligned to two
+/// characters like the puzzle input.
+fn write_numbers(f: &mut fmt::Formatter, numbers: &[u32]) -> fmt::Result {
+    let numbers: Vec<String> = numbers.iter().map(|x| format!("{:>2}", x)).collect();
+    write!(f, "{}", numbers.join(" "))
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Card {}: ", self.id)?;
+        write_numbers(f, &self.raw_numbers)?;
+        write!(f, " | ")?;
+        write_numbers(f, &self.raw_winning_numbers)
+    }
+}
+
+fn tokens(numbers: &str) -> Vec<&str> {
+    numbers.split(' ').filter(|x| !x.is_empty()).collect()
+}
+
+/// Splits a card into its id and the text of both number sections.
+fn split_card(value: &str, line: usize) -> Result<(u32, &str, &str), CardError> {
+    let error = |kind: CardErrorKind, token: &str| {
+        ParseError::new(kind, Location::of(line, value, token), token)
+    };
+
+    let (id, numbers) = value
+        .split_once(": ")
+        .ok_or_else(|| error(CardErrorKind::MissingNumbers, value))?;
+    let id = id
+        .strip_prefix("Card ")
+        .ok_or_else(|| error(CardErrorKind::MissingPrefix, id))?
+        .trim_start();
+    let id = id
+        .parse()
+        .map_err(|_| error(CardErrorKind::InvalidNumber, id))?;
+
+    let (numbers, winning_numbers) = numbers
+        .split_once(" | ")
+        .ok_or_else(|| error(CardErrorKind::MissingWinningNumbers, numbers))?;
+
+    Ok((id, numbers, winning_numbers))
+}
+
+impl ParseLine for Card {
+    type Err = CardError;
+
+    fn parse_line(value: &str, line: usize) -> Result<Self, Self::Err> {
+        let (id, numbers, winning_numbers) = split_card(value, line)?;
+        Card::parse_body(id, numbers, winning_numbers, value, line)
+    }
+}
+
+impl std::str::FromStr for Card {
+    type Err = CardError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Card::parse_line(value, 1)
+    }
+}
@@ -0,0 +1,128 @@
+use crate::card::Card;
+
+/// Copies of a card won from one earlier card.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Contribution {
+    pub id: u32,
+    pub copies: u64,
+}
+
+/// How many copies of a card were held in the end, counting the original,
+/// and which cards the won copies came from.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CardTrace {
+    pub id: u32,
+    pub copies: u64,
+    pub contributions: Vec<Contribution>,
+}
+
+/// Plays the cascade from `get_part2` and records every contribution, with
+/// the traces in id order. Unlike `get_part2` this takes time proportional
+/// to the total win count, so it is meant for explaining results.
+pub fn trace(cards: &[Card]) -> Vec<CardTrace> {
+    let mut order: Vec<&Card> = cards.iter().collect();
+    order.sort_by_key(|x| x.id);
+
+    let mut traces: Vec<CardTrace> = order
+        .iter()
+        .map(|x| CardTrace {
+            id: x.id,
+            copies: 1,
+            contributions: vec![],
+        })
+        .collect();
+
+    for (index, card) in order.iter().enumerate() {
+        let copies = traces[index].copies;
+        let last = u64::from(card.id) + card.win_count() as u64;
+        let start = order.partition_point(|x| x.id <= card.id);
+        let end = order.partition_point(|x| u64::from(x.id) <= last);
+        for won in &mut traces[start..end] {
+            won.copies += copies;
+            won.contributions.push(Contribution {
+                id: card.id,
+                copies,
+            });
+        }
+    }
+
+    traces
+}
+
+/// One row per card: its id, final copies and contributions as `id×copies`.
+pub fn render_table(traces: &[CardTrace]) -> String {
+    let width = |header: &str, values: Vec<String>| {
+        values
+            .iter()
+            .map(|x| x.len())
+            .chain([header.len()])
+            .max()
+            .unwrap_or(0)
+    };
+    let id_width = width("Card", traces.iter().map(|x| x.id.to_string()).collect());
+    let copies_width = width(
+        "Copies",
+        traces.iter().map(|x| x.copies.to_string()).collect(),
+    );
+
+    let mut lines = vec![format!(
+        "{:>id_width$}  {:>copies_width$}  From",
+        "Card", "Copies"
+    )];
+    for trace in traces {
+        let from: Vec<String> = trace
+            .contributions
+            .iter()
+            .map(|x| format!("{}×{}", x.id, x.copies))
+            .collect();
+        let line = format!(
+            "{:>id_width$}  {:>copies_width$}  {}",
+            trace.id,
+            trace.copies,
+            from.join(", ")
+        );
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+/// Each card with its final copies, branching into the original and every
+/// card that contributed copies.
+pub fn render_tree(traces: &[CardTrace]) -> String {
+    let mut lines = vec![];
+    for trace in traces {
+        lines.push(format!(
+            "Card {}: {} {}",
+            trace.id,
+            trace.copies,
+            plural(trace.copies)
+        ));
+
+        let branches: Vec<String> = std::iter::once("original".to_string())
+            .chain(
+                trace
+                    .contributions
+                    .iter()
+                    .map(|x| format!("+{} from card {}", x.copies, x.id)),
+            )
+            .collect();
+        for (index, branch) in branches.iter().enumerate() {
+            let joint = if index + 1 == branches.len() {
+                "└─"
+            } else {
+                "├─"
+            };
+            lines.push(format!("{} {}", joint, branch));
+        }
+    }
+    lines.join("\n")
+}
+
+fn plural(copies: u64) -> &'static str {
+    match copies {
+        1 => "copy",
+        _ => "copies",
+    }
+}
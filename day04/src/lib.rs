@@ -0,0 +1,471 @@
+pub mod bitset;
+pub mod card;
+pub mod cascade;
+
+use card::{Card, CardError, CardRules};
+use common::{Lines, Solution};
+use std::error::Error;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Lines<Card>;
+    type Output = u64;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        Ok(get_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        Ok(get_part2(input))
+    }
+}
+
+/// A card along with how many of its numbers won and the points scored.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CardReport<'a> {
+    pub card: &'a Card,
+    pub win_count: usize,
+    pub points: u32,
+}
+
+pub fn report(cards: &[Card]) -> Vec<CardReport<'_>> {
+    cards
+        .iter()
+        .map(|card| CardReport {
+            card,
+            win_count: card.win_count(),
+            points: card.get_points(),
+        })
+        .collect()
+}
+
+/// Parses every card with `Card::parse_strict`. Section counts not set in
+/// `rules` must match those of the first card, so every card lines up in the
+/// same columns.
+pub fn parse_strict(value: &str, rules: CardRules) -> Result<Vec<Card>, CardError> {
+    let mut rules = rules;
+    let mut cards = vec![];
+    for (index, line) in value.lines().enumerate() {
+        let card = Card::parse_strict(line, index + 1, &rules)?;
+        rules.numbers.get_or_insert(card.raw_numbers.len());
+        rules
+            .winning_numbers
+            .get_or_insert(card.raw_winning_numbers.len());
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+pub fn get_part1(cards: &[Card]) -> u32 {
+    cards.iter().map(|x| x.get_points()).sum()
+}
+
+/// Counts every card held once the copies have cascaded. A card with id
+/// `n` and `k` wins copies of the cards with ids `n + 1` to `n + k`; ids that
+/// are not in the table win nothing, and the table may be in any order.
+pub fn get_part2(cards: &[Card]) -> u64 {
+    let mut order: Vec<&Card> = cards.iter().collect();
+    order.sort_by_key(|x| x.id);
+
+    // Copies won by a card apply to a contiguous run of the sorted table, so
+    // they are added where the run starts and removed where it ends.
+    let mut starting = vec![0u64; order.len() + 1];
+    let mut ending = vec![0u64; order.len() + 1];
+    let mut won = 0u64;
+    let mut total = 0u64;
+
+    for (index, card) in order.iter().enumerate() {
+        won += starting[index];
+        won -= ending[index];
+        let copies = won + 1;
+        total += copies;
+
+        let last = u64::from(card.id) + card.win_count() as u64;
+        let start = order.partition_point(|x| x.id <= card.id);
+        let end = order.partition_point(|x| u64::from(x.id) <= last);
+        if start < end {
+            starting[start] += copies;
+            ending[end] += copies;
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bitset::NumberSet;
+    use card::CardErrorKind;
+    use cascade::{render_table, render_tree, trace};
+    use common::rng::Rng;
+    use common::{Location, ParseError, ParseLine};
+    use std::collections::{HashMap, HashSet};
+
+    fn card(id: u32, wins: usize) -> Card {
+        let numbers: Vec<u32> = (0..wins as u32).collect();
+        let winning_numbers: Vec<u32> = (0..10).collect();
+        Card {
+            id,
+            numbers: numbers.clone().into(),
+            winning_numbers: winning_numbers.clone().into(),
+            raw_numbers: numbers,
+            raw_winning_numbers: winning_numbers,
+        }
+    }
+
+    /// Plays the cards one copy at a time, straight from the puzzle text.
+    fn naive_part2(cards: &[Card]) -> u64 {
+        let by_id: HashMap<u32, &Card> = cards.iter().map(|x| (x.id, x)).collect();
+        let mut pending: Vec<u32> = cards.iter().map(|x| x.id).collect();
+        let mut total = 0;
+        while let Some(id) = pending.pop() {
+            total += 1;
+            let wins = by_id[&id].win_count() as u32;
+            pending.extend((id + 1..=id + wins).filter(|x| by_id.contains_key(x)));
+        }
+        total
+    }
+
+    #[test]
+    fn test_example() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+        let cards: Vec<Card> = example
+            .iter()
+            .map(|x| x.parse())
+            .collect::<Result<Vec<Card>, _>>()?;
+
+        let points: Vec<u32> = cards.iter().map(|x| x.get_points()).collect();
+
+        assert_eq!(points, [8, 2, 2, 1, 0, 0]);
+        let part1 = get_part1(&cards);
+        assert_eq!(part1, 13);
+
+        let part2 = get_part2(&cards);
+        assert_eq!(part2, 30);
+
+        let win_counts: Vec<usize> = report(&cards).iter().map(|x| x.win_count).collect();
+        assert_eq!(win_counts, [4, 2, 2, 1, 0, 0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2_overflows_u32() {
+        // Every card wins the next two, so the copies grow like Fibonacci
+        // numbers and the last cards win nothing past the end of the table.
+        let cards: Vec<Card> = (1..=60).map(|id| card(id, 2)).collect();
+        let total = get_part2(&cards);
+        assert!(total > u64::from(u32::MAX));
+        assert_eq!(total, 10610209857660);
+
+        let cards: Vec<Card> = (1..=25).map(|id| card(id, 2)).collect();
+        assert_eq!(get_part2(&cards), naive_part2(&cards));
+    }
+
+    #[test]
+    fn test_part2_out_of_order_and_missing_ids() {
+        let cards = [card(4, 0), card(1, 2), card(5, 1), card(2, 3)];
+        // 1 wins 2 and the missing 3; 2 wins the missing 3, 4 and 5; 5 wins
+        // the missing 6.
+        assert_eq!(get_part2(&cards), 1 + 2 + 3 + 3);
+
+        let mut rng = Rng::new(20);
+        for _ in 0..200 {
+            let mut cards: Vec<Card> = vec![];
+            for id in 1..rng.range(2, 30) as u32 {
+                if rng.range(0, 4) != 0 {
+                    cards.push(card(id, rng.range(0, 5) as usize));
+                }
+            }
+            for index in (1..cards.len()).rev() {
+                cards.swap(index, rng.range(0, index as u64 + 1) as usize);
+            }
+            assert_eq!(get_part2(&cards), naive_part2(&cards), "{:?}", cards);
+        }
+    }
+
+    #[test]
+    fn test_number_set() {
+        let small: NumberSet = [5, 127, 0, 5].into_iter().collect();
+        assert!(small.is_compact());
+        assert_eq!(small.iter().collect::<Vec<_>>(), [0, 5, 127]);
+        assert!(small.contains(127) && !small.contains(128) && !small.contains(4));
+
+        let large: NumberSet = [300, 5, 128, 5].into_iter().collect();
+        assert!(!large.is_compact());
+        assert_eq!(large.len(), 3);
+        assert_eq!(large.iter().collect::<Vec<_>>(), [5, 128, 300]);
+
+        assert_eq!(small.intersection_count(&large), 1);
+        assert_eq!(large.intersection_count(&small), 1);
+        assert!(NumberSet::from(vec![]).is_empty());
+
+        let mut rng = Rng::new(21);
+        for _ in 0..500 {
+            let limit = *rng.choose(&[20, 128, 129, 1000]);
+            let a: Vec<u32> = (0..rng.range(0, 30))
+                .map(|_| rng.range(0, limit) as u32)
+                .collect();
+            let b: Vec<u32> = (0..rng.range(0, 30))
+                .map(|_| rng.range(0, limit) as u32)
+                .collect();
+
+            let expected = a
+                .iter()
+                .collect::<HashSet<_>>()
+                .intersection(&b.iter().collect())
+                .count();
+            let (a, b): (NumberSet, NumberSet) = (a.into(), b.into());
+            assert_eq!(a.intersection_count(&b), expected, "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_cascade_trace() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+        let cards: Vec<Card> = example
+            .iter()
+            .map(|x| x.parse())
+            .collect::<Result<_, _>>()?;
+
+        let traces = trace(&cards);
+        let copies: Vec<u64> = traces.iter().map(|x| x.copies).collect();
+        assert_eq!(copies, [1, 2, 4, 8, 14, 1]);
+
+        assert_eq!(
+            render_table(&traces[..4]),
+            [
+                "Card  Copies  From",
+                "   1       1",
+                "   2       2  1×1",
+                "   3       4  1×1, 2×2",
+                "   4       8  1×1, 2×2, 3×4",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            render_tree(&traces[..2]),
+            [
+                "Card 1: 1 copy",
+                "└─ original",
+                "Card 2: 2 copies",
+                "├─ original",
+                "└─ +1 from card 1",
+            ]
+            .join("\n")
+        );
+
+        let mut rng = Rng::new(23);
+        for _ in 0..100 {
+            let mut cards: Vec<Card> = vec![];
+            for id in 1..rng.range(2, 20) as u32 {
+                if rng.range(0, 4) != 0 {
+                    cards.push(card(id, rng.range(0, 5) as usize));
+                }
+            }
+            cards.reverse();
+            let total: u64 = trace(&cards).iter().map(|x| x.copies).sum();
+            assert_eq!(total, get_part2(&cards));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let location = |line, column| Location { line, column };
+
+        let error = "Crd 1: 41 48 | 83 86".parse::<Card>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(CardErrorKind::MissingPrefix, location(1, 1), "Crd 1")
+        );
+
+        let error = "Card 1 41 48 | 83 86".parse::<Card>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                CardErrorKind::MissingNumbers,
+                location(1, 1),
+                "Card 1 41 48 | 83 86"
+            )
+        );
+
+        let error = "Card 1: 41 48 83 86".parse::<Card>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                CardErrorKind::MissingWinningNumbers,
+                location(1, 9),
+                "41 48 83 86"
+            )
+        );
+
+        let error = Card::parse_line("Card  12: 41 4x | 83 86", 12).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(CardErrorKind::InvalidNumber, location(12, 14), "4x")
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 12, column 14: invalid number \"4x\""
+        );
+    }
+
+    #[test]
+    fn test_display_example() -> Result<(), Box<dyn Error>> {
+        let card: Card = "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1".parse()?;
+        assert_eq!(
+            card.to_string(),
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"
+        );
+
+        let card: Card = "Card  7: 5 5 10 | 8   3".parse()?;
+        assert_eq!(card.raw_numbers, [5, 5, 10]);
+        assert_eq!(card.numbers.len(), 2);
+        assert_eq!(card.to_string(), "Card 7:  5  5 10 |  8  3");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_strict() -> Result<(), Box<dyn Error>> {
+        let location = |line, column| Location { line, column };
+        let rules = CardRules::default();
+
+        let cards = parse_strict("Card 1: 1 2 | 3 4 5\nCard 2: 6 7 | 1 2 3", rules)?;
+        assert_eq!(cards.len(), 2);
+
+        let error = parse_strict("Card 1: 1 2 | 3 4 5\nCard 7: 6 7 | 1 2 1", rules);
+        assert_eq!(
+            error.unwrap_err(),
+            ParseError::new(CardErrorKind::DuplicateNumber(7), location(2, 19), "1")
+        );
+        let error = parse_strict("Card 1: 1 2 | 3 4 5\nCard 7: 6 7 8 | 1 2 3", rules);
+        assert_eq!(
+            error.unwrap_err(),
+            ParseError::new(CardErrorKind::TooManyNumbers(7), location(2, 13), "8")
+        );
+        let error = parse_strict("Card 1: 1 2 | 3 4 5\nCard 7: 6 7 | 1 2", rules);
+        let error = error.unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(CardErrorKind::TooFewNumbers(7), location(2, 15), "1 2")
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 15: card 7 has too few numbers in \"1 2\""
+        );
+
+        let rules = CardRules {
+            numbers: Some(3),
+            winning_numbers: None,
+        };
+        let error = parse_strict("Card 1: 1 2 | 3 4 5", rules).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(CardErrorKind::TooFewNumbers(1), location(1, 9), "1 2")
+        );
+
+        let error = parse_strict("Card 1: 1 x | 3 4 4", CardRules::default());
+        assert_eq!(
+            error.unwrap_err(),
+            ParseError::new(CardErrorKind::InvalidNumber, location(1, 11), "x")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_round_trip() -> Result<(), Box<dyn Error>> {
+        let mut rng = Rng::new(4);
+
+        for _ in 0..500 {
+            let numbers = |rng: &mut Rng, count| {
+                (0..count)
+                    .map(|_| format!("{:>2}", rng.range(0, 150)))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            };
+            let id = rng.range(1, 300);
+            let count = rng.range(0, 10);
+            let numbers_text = numbers(&mut rng, count);
+            let count = rng.range(0, 25);
+            let line = format!(
+                "Card {:>3}: {} | {}",
+                id,
+                numbers_text,
+                numbers(&mut rng, count)
+            );
+
+            let card: Card = line.parse()?;
+            let canonical = card.to_string();
+            let reparsed: Card = canonical.parse()?;
+            assert_eq!(reparsed, card, "{}", line);
+            assert_eq!(reparsed.to_string(), canonical);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_report_json() -> Result<(), Box<dyn Error>> {
+        let card = Card::parse_line("Card 3: 83 41 41 | 41 200 83 9", 1)?;
+        let json = serde_json::to_value(report(&[card]))?;
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "card": {
+                    "id": 3,
+                    "numbers": [41, 83],
+                    "winning_numbers": [9, 41, 83, 200],
+                    "raw_numbers": [83, 41, 41],
+                    "raw_winning_numbers": [41, 200, 83, 9],
+                },
+                "win_count": 2,
+                "points": 2,
+            }])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_json_round_trip() -> Result<(), Box<dyn Error>> {
+        let cards = [
+            Card::parse_line("Card 3: 83 41 41 | 41 200 83 9", 1)?,
+            Card::parse_line("Card 4:  1 | 2", 2)?,
+        ];
+        let json = serde_json::to_string(&cards)?;
+        let imported: Vec<Card> = serde_json::from_str(&json)?;
+        assert_eq!(imported, cards);
+
+        // The sets always come from the raw lists, whatever the input says.
+        let json =
+            r#"{"id": 1, "numbers": [5], "raw_numbers": [1, 2], "raw_winning_numbers": [2]}"#;
+        let imported: Card = serde_json::from_str(json)?;
+        assert_eq!(imported, Card::parse_line("Card 1: 1 2 | 2", 1)?);
+        assert_eq!(imported.win_count(), 1);
+
+        Ok(())
+    }
+}
@@ -1,74 +1,93 @@
-mod card;
-
-use card::Card;
-use std::collections::HashMap;
+use common::{Emit, Solution, read_stdin};
+use day04::Day04;
+use day04::card::{Card, CardRules};
+use day04::cascade::{render_table, render_tree, trace};
 use std::error::Error;
-use std::io::Read;
+
+const USAGE: &str = "Usage: day04 [--strict] [--explain table|tree] [--emit json] < input.txt";
+
+/// How `--explain` lays out the copies each card ended up with.
+enum Explain {
+    Table,
+    Tree,
+}
+
+struct Args {
+    strict: bool,
+    explain: Option<Explain>,
+    emit: Option<Emit>,
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut buffer = String::new();
-    std::io::stdin().read_to_string(&mut buffer)?;
+    let args = parse_args(std::env::args().skip(1))?;
+
+    let buffer = read_stdin()?;
+    let cards: Vec<Card> = if args.strict {
+        day04::parse_strict(&buffer, CardRules::default())?
+    } else {
+        Day04::parse(&buffer)?.0
+    };
+
+    if args.emit.is_some() {
+        return emit_json(&cards);
+    }
 
-    let cards: Vec<Card> = buffer
-        .lines()
-        .map(|x| x.parse())
-        .collect::<Result<Vec<Card>, _>>()?;
+    match args.explain {
+        Some(Explain::Table) => println!("{}", render_table(&trace(&cards))),
+        Some(Explain::Tree) => println!("{}", render_tree(&trace(&cards))),
+        None => {}
+    }
 
-    let part1 = get_part1(&cards);
+    let part1 = day04::get_part1(&cards);
     println!("Part 1: {}", part1);
 
-    let part2 = get_part2(&cards);
+    let part2 = day04::get_part2(&cards);
     println!("Part 2: {}", part2);
 
     Ok(())
 }
 
-fn get_part1(cards: &[Card]) -> u32 {
-    cards.iter().map(|x| x.get_points()).sum()
+#[cfg(feature = "serde")]
+fn emit_json(cards: &[Card]) -> Result<(), Box<dyn Error>> {
+    common::print_json(&day04::report(cards))
 }
 
-fn get_part2(cards: &[Card]) -> u32 {
-    let mut card_map: HashMap<u32, usize> = HashMap::new();
-    for card in cards {
-        let copies = *card_map.entry(card.id).or_insert(1);
-        let win_count = card.win_count();
-        for index in 1..win_count + 1 {
-            let key = card.id + (index as u32);
-            let value = card_map.entry(key).or_insert(1);
-            *value += copies;
-        }
-    }
-    card_map.values().map(|x| *x as u32).sum::<u32>()
+#[cfg(not(feature = "serde"))]
+fn emit_json(_cards: &[Card]) -> Result<(), Box<dyn Error>> {
+    Err(common::EMIT_UNAVAILABLE.into())
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_example() -> Result<(), Box<dyn Error>> {
-        let example = [
-            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
-            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
-            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
-            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
-            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
-            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
-        ];
-        let cards: Vec<Card> = example
-            .iter()
-            .map(|x| x.parse())
-            .collect::<Result<Vec<Card>, _>>()?;
-
-        let points: Vec<u32> = cards.iter().map(|x| x.get_points()).collect();
+fn parse_args<I>(args: I) -> Result<Args, Box<dyn Error>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut parsed = Args {
+        strict: false,
+        explain: None,
+        emit: None,
+    };
+    let mut args = args.into_iter();
 
-        assert_eq!(points, [8, 2, 2, 1, 0, 0]);
-        let part1 = get_part1(&cards);
-        assert_eq!(part1, 13);
-
-        let part2 = get_part2(&cards);
-        assert_eq!(part2, 30);
-
-        Ok(())
+    while let Some(arg) = args.next() {
+        if arg == "--strict" {
+            parsed.strict = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}\n{}", arg, USAGE))?;
+        match arg.as_str() {
+            "--explain" => {
+                parsed.explain = match value.as_str() {
+                    "table" => Some(Explain::Table),
+                    "tree" => Some(Explain::Tree),
+                    x => return Err(format!("Unknown explain format {}\n{}", x, USAGE).into()),
+                }
+            }
+            "--emit" => parsed.emit = common::parse_emit([arg, value])?,
+            x => return Err(format!("Unexpected argument {}\n{}", x, USAGE).into()),
+        };
     }
+
+    Ok(parsed)
 }
@@ -1,78 +1,647 @@
-use std::collections::HashMap;
+use common::{Location, ParseError};
+use std::collections::HashSet;
 use std::error::Error;
+use std::fmt;
+use std::ops::Range;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "AlmanacRecord")
+)]
 pub struct Almanac {
     seeds: Vec<u64>,
-    maps: HashMap<String, Vec<AlmanacMap>>,
+    maps: Vec<MapSection>,
+    /// The sections from seed to location, looked up once when parsing.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    seed_to_location: Result<Vec<usize>, ChainError>,
 }
 
-#[derive(Debug)]
+/// The fields an almanac is read back from. Entries are checked the same
+/// way as when parsing, and the seed to location path is looked up again.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AlmanacRecord {
+    seeds: Vec<u64>,
+    maps: Vec<MapSection>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AlmanacRecord> for Almanac {
+    type Error = String;
+
+    fn try_from(record: AlmanacRecord) -> Result<Self, Self::Error> {
+        for section in &record.maps {
+            for entry in &section.entries {
+                let fits = |start: u64| start.checked_add(entry.range_length).is_some();
+                if !fits(entry.destination_range_start) || !fits(entry.source_range_start) {
+                    return Err(format!(
+                        "{}-to-{} entry {} {} {} runs past {}",
+                        section.source,
+                        section.destination,
+                        entry.destination_range_start,
+                        entry.source_range_start,
+                        entry.range_length,
+                        u64::MAX
+                    ));
+                }
+            }
+        }
+        Ok(Almanac::new(record.seeds, record.maps))
+    }
+}
+
+/// One `source-to-destination map:` section of the almanac.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct MapSection {
+    source: String,
+    destination: String,
+    entries: Vec<AlmanacMap>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct AlmanacMap {
     destination_range_start: u64,
     source_range_start: u64,
     range_length: u64,
 }
 
+/// What was wrong with the token an almanac failed to parse at.
+#[derive(Debug, PartialEq)]
+pub enum AlmanacErrorKind {
+    MissingSeedsPrefix,
+    MissingMapSuffix,
+    InvalidMapName,
+    MissingSourceStart,
+    MissingRangeLength,
+    InvalidNumber,
+    RangeOverflow,
+}
+
+impl fmt::Display for AlmanacErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlmanacErrorKind::MissingSeedsPrefix => write!(f, "missing \"seeds: \" prefix in"),
+            AlmanacErrorKind::MissingMapSuffix => write!(f, "missing \" map:\" suffix in"),
+            AlmanacErrorKind::InvalidMapName => write!(f, "expected source-to-destination in"),
+            AlmanacErrorKind::MissingSourceStart => write!(f, "missing source range start in"),
+            AlmanacErrorKind::MissingRangeLength => write!(f, "missing range length in"),
+            AlmanacErrorKind::InvalidNumber => write!(f, "invalid number"),
+            AlmanacErrorKind::RangeOverflow => write!(f, "range runs past {} in", u64::MAX),
+        }
+    }
+}
+
+/// Why an almanac failed to parse.
+pub type AlmanacError = ParseError<AlmanacErrorKind>;
+
+/// Why there is no single chain of maps between two categories.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainError {
+    UnknownCategory(String),
+    /// The categories around a cycle, starting and ending with the same one.
+    Cycle(Vec<String>),
+    /// A category with no map out of it, met before reaching the target.
+    DeadEnd(String, String),
+    /// Two different paths to the target, as lists of categories.
+    Ambiguous(Vec<String>, Vec<String>),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChainError::UnknownCategory(category) => {
+                write!(f, "unknown category {:?}", category)
+            }
+            ChainError::Cycle(categories) => {
+                write!(f, "maps form a cycle: {}", categories.join(" -> "))
+            }
+            ChainError::DeadEnd(category, target) => {
+                write!(f, "no map out of {:?} on the way to {:?}", category, target)
+            }
+            ChainError::Ambiguous(first, second) => write!(
+                f,
+                "ambiguous paths: {} and {}",
+                first.join(" -> "),
+                second.join(" -> ")
+            ),
+        }
+    }
+}
+
+impl Error for ChainError {}
+
+/// State for the depth first search behind `Almanac::chain`.
+struct Search<'a> {
+    target: &'a str,
+    reaching: HashSet<&'a str>,
+    visiting: Vec<&'a str>,
+    sections: Vec<usize>,
+    paths: Vec<Vec<usize>>,
+}
+
+/// The entries of every map on the path between two categories, in order.
+/// Looking the path up once lets any number of values go through it.
+#[derive(Debug)]
+pub struct Chain<'a> {
+    steps: Vec<&'a [AlmanacMap]>,
+}
+
+impl Chain<'_> {
+    /// Maps a single value from the first category to the last.
+    pub fn map_value(&self, value: u64) -> u64 {
+        let mut current = value;
+        for map_ranges in &self.steps {
+            current = map_ranges
+                .iter()
+                .find(|x| x.source_range().contains(&current))
+                .map_or(current, |x| {
+                    x.destination_range_start + (current - x.source_range_start)
+                });
+        }
+        current
+    }
+
+    /// Maps `ranges` of values from the first category to the last.
+    pub fn map_ranges(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        let mut current = ranges;
+        for map_ranges in &self.steps {
+            current = current
+                .into_iter()
+                .flat_map(|range| map_range(range, map_ranges))
+                .collect();
+        }
+        current
+    }
+
+    /// The values of the first category that map into `ranges` of the last,
+    /// sorted and merged where they touch.
+    pub fn unmap_ranges(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        let mut current = ranges;
+        for map_ranges in self.steps.iter().rev() {
+            current = current
+                .into_iter()
+                .filter(|range| !range.is_empty())
+                .flat_map(|range| unmap_range(range, map_ranges))
+                .collect();
+        }
+        merge(current)
+    }
+}
+
 impl Almanac {
+    fn new(seeds: Vec<u64>, maps: Vec<MapSection>) -> Almanac {
+        let mut almanac = Almanac {
+            seeds,
+            maps,
+            seed_to_location: Ok(vec![]),
+        };
+        almanac.seed_to_location = almanac.search("seed", "location");
+        almanac
+    }
+
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
     pub fn map_all_seeds_to_location(&self) -> Result<Vec<u64>, Box<dyn Error>> {
-        self.seeds
-            .iter()
-            .map(|x| self.map_seed_to_location(*x))
-            .collect()
+        let chain = self.seed_chain()?;
+        Ok(self.seeds.iter().map(|x| chain.map_value(*x)).collect())
+    }
+
+    /// Reads `seeds` as (start, length) pairs, dropping empty ranges.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<u64>>, Box<dyn Error>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err("seed ranges must come in (start, length) pairs".into());
+        }
+
+        let mut ranges = vec![];
+        for pair in self.seeds.chunks(2) {
+            let end = pair[0].checked_add(pair[1]).ok_or_else(|| {
+                format!("seed range {} {} runs past {}", pair[0], pair[1], u64::MAX)
+            })?;
+            if pair[0] < end {
+                ranges.push(pair[0]..end);
+            }
+        }
+        Ok(ranges)
+    }
+
+    /// Treats `seeds` as (start, length) pairs and maps every range through
+    /// each step as a whole, splitting ranges where they cross map boundaries.
+    pub fn map_seed_ranges_to_location(&self) -> Result<Vec<Range<u64>>, Box<dyn Error>> {
+        Ok(self.seed_chain()?.map_ranges(self.seed_ranges()?))
+    }
+
+    /// Maps `ranges` of `from` values to the `to` values they correspond to.
+    pub fn map_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: Vec<Range<u64>>,
+    ) -> Result<Vec<Range<u64>>, ChainError> {
+        Ok(self.chain(from, to)?.map_ranges(ranges))
+    }
+
+    /// Maps a single `from` value to the `to` value it corresponds to.
+    pub fn map_value(&self, from: &str, to: &str, value: u64) -> Result<u64, ChainError> {
+        Ok(self.chain(from, to)?.map_value(value))
+    }
+
+    /// The categories on the only path of maps from `from` to `to`,
+    /// including both ends.
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<String>, ChainError> {
+        Ok(self.categories(from, &self.search(from, to)?))
+    }
+
+    /// Every seed that ends up at `location`. Maps need not be one to one,
+    /// so there can be several, or none at all.
+    pub fn map_location_to_seed(&self, location: u64) -> Result<Vec<u64>, Box<dyn Error>> {
+        // No range can hold u64::MAX, since entries must end by then, so
+        // only the identity gaps lead there and only from u64::MAX itself.
+        if location == u64::MAX {
+            self.seed_chain()?;
+            return Ok(vec![location]);
+        }
+        Ok(self
+            .map_location_range_to_seed(location..location + 1)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// The seeds that end up somewhere in `locations`, walking every step
+    /// backwards. The ranges are sorted and merged where they touch.
+    pub fn map_location_range_to_seed(
+        &self,
+        locations: Range<u64>,
+    ) -> Result<Vec<Range<u64>>, Box<dyn Error>> {
+        Ok(self.seed_chain()?.unmap_ranges(vec![locations]))
+    }
+
+    /// The `from` values that map into `ranges` of `to` values, sorted and
+    /// merged where they touch.
+    pub fn unmap_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: Vec<Range<u64>>,
+    ) -> Result<Vec<Range<u64>>, ChainError> {
+        Ok(self.chain(from, to)?.unmap_ranges(ranges))
+    }
+
+    pub fn map_seed_to_location(&self, seed: u64) -> Result<u64, Box<dyn Error>> {
+        Ok(self.seed_chain()?.map_value(seed))
+    }
+
+    /// Looks up the only path of maps from `from` to `to`.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Chain<'_>, ChainError> {
+        Ok(self.steps(&self.search(from, to)?))
+    }
+
+    /// The chain from seed to location, without searching for it again.
+    fn seed_chain(&self) -> Result<Chain<'_>, ChainError> {
+        match &self.seed_to_location {
+            Ok(sections) => Ok(self.steps(sections)),
+            Err(error) => Err(error.clone()),
+        }
+    }
+
+    fn steps(&self, sections: &[usize]) -> Chain<'_> {
+        Chain {
+            steps: sections
+                .iter()
+                .map(|x| self.maps[*x].entries.as_slice())
+                .collect(),
+        }
+    }
+
+    /// Finds the only path of sections from `from` to `to`, treating each
+    /// section as an edge between its categories. Paths never visit a
+    /// category twice, so cycles only matter when they keep the search from
+    /// reaching `to` at all.
+    fn search(&self, from: &str, to: &str) -> Result<Vec<usize>, ChainError> {
+        for category in [from, to] {
+            let known = self
+                .maps
+                .iter()
+                .any(|x| x.source == category || x.destination == category);
+            if !known && from != to {
+                return Err(ChainError::UnknownCategory(category.to_string()));
+            }
+        }
+
+        let reaching = self.reaching(to);
+        if !reaching.contains(from) {
+            return Err(self.unreachable(from, to));
+        }
+
+        let mut search = Search {
+            target: to,
+            reaching,
+            visiting: vec![from],
+            sections: vec![],
+            paths: vec![],
+        };
+        self.visit(from, &mut search);
+
+        match &search.paths[..] {
+            [sections] => match self.cycle_along(sections, &search) {
+                Some(cycle) => Err(ChainError::Cycle(cycle)),
+                None => Ok(sections.clone()),
+            },
+            [first, second, ..] => Err(ChainError::Ambiguous(
+                self.categories(from, first),
+                self.categories(from, second),
+            )),
+            [] => Err(self.unreachable(from, to)),
+        }
     }
 
-    fn map_seed_to_location(&self, seed: u64) -> Result<u64, Box<dyn Error>> {
-        const MAP_STEPS: [&str; 7] = [
-            "seed-to-soil",
-            "soil-to-fertilizer",
-            "fertilizer-to-water",
-            "water-to-light",
-            "light-to-temperature",
-            "temperature-to-humidity",
-            "humidity-to-location",
-        ];
-        let mut current = seed as i64;
-
-        for step in MAP_STEPS {
-            let map_ranges = self.maps.get(step).ok_or(format!("missing {} map", step))?;
-
-            let mut next = current;
-            for map_range in map_ranges {
-                let destination_range_start = map_range.destination_range_start as i64;
-                let source_range_start = map_range.source_range_start as i64;
-                let range_length = map_range.range_length as i64;
-                let difference: i64 = current - source_range_start;
-                if difference >= 0 && difference < range_length {
-                    next = destination_range_start + difference;
-                    break;
+    /// Collects paths from `category` to the target until there are two,
+    /// only stepping into categories that can still reach it.
+    fn visit<'a>(&'a self, category: &'a str, search: &mut Search<'a>) {
+        if category == search.target {
+            search.paths.push(search.sections.clone());
+            return;
+        }
+
+        for index in self.sections_from(category) {
+            let destination = self.maps[index].destination.as_str();
+            if search.paths.len() > 1
+                || !search.reaching.contains(destination)
+                || search.visiting.contains(&destination)
+            {
+                continue;
+            }
+            search.visiting.push(destination);
+            search.sections.push(index);
+            self.visit(destination, search);
+            search.sections.pop();
+            search.visiting.pop();
+        }
+    }
+
+    /// A cycle that goes around a step of the path, so that values could
+    /// loop through it any number of times on the way to the target.
+    fn cycle_along(&self, sections: &[usize], search: &Search) -> Option<Vec<String>> {
+        sections.iter().find_map(|x| {
+            let section = &self.maps[*x];
+            let back = self.route(
+                &section.destination,
+                &section.source,
+                search,
+                &mut HashSet::new(),
+            )?;
+            Some(
+                std::iter::once(section.source.clone())
+                    .chain(back)
+                    .collect(),
+            )
+        })
+    }
+
+    /// The categories on some path of maps from `category` to `end`, only
+    /// going through categories that can reach the target of `search`.
+    fn route<'a>(
+        &'a self,
+        category: &'a str,
+        end: &str,
+        search: &Search,
+        seen: &mut HashSet<&'a str>,
+    ) -> Option<Vec<String>> {
+        if category == end {
+            return Some(vec![category.to_string()]);
+        }
+        if category == search.target || !seen.insert(category) {
+            return None;
+        }
+
+        self.sections_from(category).find_map(|index| {
+            let destination = self.maps[index].destination.as_str();
+            if !search.reaching.contains(destination) {
+                return None;
+            }
+            let mut rest = self.route(destination, end, search, seen)?;
+            rest.insert(0, category.to_string());
+            Some(rest)
+        })
+    }
+
+    /// Every category with a path of maps to `target`, including itself.
+    /// Paths stop at `target`, so sections out of it are never followed.
+    fn reaching<'a>(&'a self, target: &'a str) -> HashSet<&'a str> {
+        let mut reaching = HashSet::from([target]);
+        loop {
+            let before = reaching.len();
+            for section in &self.maps {
+                if section.source != target && reaching.contains(section.destination.as_str()) {
+                    reaching.insert(&section.source);
+                }
+            }
+            if reaching.len() == before {
+                return reaching;
+            }
+        }
+    }
+
+    /// Explains why `to` cannot be reached from `from`: the first category
+    /// met without a map out of it or, when there is none, the cycle the
+    /// maps lead around instead.
+    fn unreachable(&self, from: &str, to: &str) -> ChainError {
+        let mut seen = HashSet::from([from]);
+        let mut pending = vec![from];
+        while let Some(category) = pending.pop() {
+            let sections: Vec<usize> = self.sections_from(category).collect();
+            if sections.is_empty() {
+                return ChainError::DeadEnd(category.to_string(), to.to_string());
+            }
+            for section in sections.into_iter().rev() {
+                let destination = self.maps[section].destination.as_str();
+                if seen.insert(destination) {
+                    pending.push(destination);
                 }
             }
-            current = next;
         }
 
-        Ok(current as u64)
+        ChainError::Cycle(self.find_cycle(from, &mut vec![]).unwrap_or_default())
+    }
+
+    /// The first cycle met walking depth first from `category`, as the
+    /// categories around it.
+    fn find_cycle<'a>(
+        &'a self,
+        category: &'a str,
+        visiting: &mut Vec<&'a str>,
+    ) -> Option<Vec<String>> {
+        if let Some(index) = visiting.iter().position(|x| *x == category) {
+            let mut cycle: Vec<String> = visiting[index..].iter().map(|x| x.to_string()).collect();
+            cycle.push(category.to_string());
+            return Some(cycle);
+        }
+
+        visiting.push(category);
+        for section in self.sections_from(category) {
+            if let Some(cycle) = self.find_cycle(&self.maps[section].destination, visiting) {
+                return Some(cycle);
+            }
+        }
+        visiting.pop();
+        None
+    }
+
+    /// The indices of the sections out of `category`.
+    fn sections_from<'a>(&'a self, category: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.maps
+            .iter()
+            .enumerate()
+            .filter(move |(_, x)| x.source == category)
+            .map(|(index, _)| index)
     }
+
+    /// The categories along `sections`, starting at `from`.
+    fn categories(&self, from: &str, sections: &[usize]) -> Vec<String> {
+        std::iter::once(from)
+            .chain(sections.iter().map(|x| self.maps[*x].destination.as_str()))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl AlmanacMap {
+    /// The same entry read from destination back to source.
+    fn inverse(&self) -> AlmanacMap {
+        AlmanacMap {
+            destination_range_start: self.source_range_start,
+            source_range_start: self.destination_range_start,
+            range_length: self.range_length,
+        }
+    }
+
+    fn source_range(&self) -> Range<u64> {
+        self.source_range_start..self.source_range_start + self.range_length
+    }
+
+    /// Splits `range` into the part covered by this map (already shifted to
+    /// the destination) and the parts left uncovered on either side.
+    fn split(&self, range: &Range<u64>) -> (Option<Range<u64>>, Vec<Range<u64>>) {
+        let source = self.source_range();
+        let start = range.start.max(source.start);
+        let end = range.end.min(source.end);
+        if start >= end {
+            return (None, vec![range.clone()]);
+        }
+
+        let mut unmapped = vec![];
+        if range.start < start {
+            unmapped.push(range.start..start);
+        }
+        if end < range.end {
+            unmapped.push(end..range.end);
+        }
+
+        let offset = self.destination_range_start;
+        let mapped = offset + (start - source.start)..offset + (end - source.start);
+        (Some(mapped), unmapped)
+    }
+}
+
+fn map_range(range: Range<u64>, map_ranges: &[AlmanacMap]) -> Vec<Range<u64>> {
+    let mut mapped = vec![];
+    let mut pending = vec![range];
+
+    for map_range in map_ranges {
+        let mut remaining = vec![];
+        for range in pending {
+            let (overlap, unmapped) = map_range.split(&range);
+            mapped.extend(overlap);
+            remaining.extend(unmapped);
+        }
+        pending = remaining;
+    }
+
+    mapped.extend(pending);
+    mapped
+}
+
+/// The values that one step maps into `range`: those an entry shifts into
+/// it, plus those no entry covers, which map to themselves. Like
+/// `map_range`, only the first entry covering a value applies to it.
+fn unmap_range(range: Range<u64>, map_ranges: &[AlmanacMap]) -> Vec<Range<u64>> {
+    let uncovered = |ranges: Vec<Range<u64>>, entries: &[AlmanacMap]| {
+        entries.iter().fold(ranges, |ranges, entry| {
+            ranges.iter().flat_map(|x| entry.split(x).1).collect()
+        })
+    };
+
+    let mut sources = vec![];
+    for (index, map_range) in map_ranges.iter().enumerate() {
+        let (overlap, _) = map_range.inverse().split(&range);
+        sources.extend(uncovered(
+            overlap.into_iter().collect(),
+            &map_ranges[..index],
+        ));
+    }
+    sources.extend(uncovered(vec![range], map_ranges));
+    sources
+}
+
+/// Sorts `ranges` and joins any that overlap or touch.
+fn merge(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.sort_by_key(|x| (x.start, x.end));
+    let mut merged: Vec<Range<u64>> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
 }
 
-fn get_map(map_section: &str) -> Result<(String, Vec<AlmanacMap>), Box<dyn Error>> {
+/// Parses one map section, which must be a slice of the full almanac `text`.
+fn get_map(text: &str, map_section: &str) -> Result<MapSection, AlmanacError> {
+    let error = |kind: AlmanacErrorKind, token: &str| {
+        ParseError::new(kind, Location::find(text, token), token)
+    };
+    let parse = |token: &str| {
+        token
+            .parse::<u64>()
+            .map_err(|_| error(AlmanacErrorKind::InvalidNumber, token))
+    };
+
     let mut maps: Vec<AlmanacMap> = vec![];
     let mut lines = map_section.lines();
-    let name = lines
-        .next()
-        .ok_or("Missing map name")?
+    let header = lines.next().unwrap_or(map_section);
+    let name = header
         .strip_suffix(" map:")
-        .ok_or("Missing map suffix")?
-        .to_string();
+        .ok_or_else(|| error(AlmanacErrorKind::MissingMapSuffix, header))?;
+    let (source, destination) = name
+        .split_once("-to-")
+        .filter(|(source, destination)| !source.is_empty() && !destination.is_empty())
+        .ok_or_else(|| error(AlmanacErrorKind::InvalidMapName, name))?;
 
     for map_entry in lines {
         let mut tokens = map_entry.split(' ');
-        let destination_range_start: u64 = tokens
-            .next()
-            .ok_or("missing destination range start")?
-            .parse()?;
-        let source_range_start: u64 = tokens.next().ok_or("missing source range start")?.parse()?;
-        let range_length: u64 = tokens.next().ok_or("missing range length")?.parse()?;
+        let destination_range_start = parse(tokens.next().unwrap_or(map_entry))?;
+        let source_range_start = parse(
+            tokens
+                .next()
+                .ok_or_else(|| error(AlmanacErrorKind::MissingSourceStart, map_entry))?,
+        )?;
+        let range_length = parse(
+            tokens
+                .next()
+                .ok_or_else(|| error(AlmanacErrorKind::MissingRangeLength, map_entry))?,
+        )?;
+
+        let fits = |start: u64| start.checked_add(range_length).is_some();
+        if !fits(destination_range_start) || !fits(source_range_start) {
+            return Err(error(AlmanacErrorKind::RangeOverflow, map_entry));
+        }
 
         maps.push(AlmanacMap {
             destination_range_start,
@@ -81,30 +650,70 @@ fn get_map(map_section: &str) -> Result<(String, Vec<AlmanacMap>), Box<dyn Error
         });
     }
 
-    Ok((name, maps))
+    Ok(MapSection {
+        source: source.to_string(),
+        destination: destination.to_string(),
+        entries: maps,
+    })
+}
+
+impl fmt::Display for Almanac {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seeds: Vec<String> = self.seeds.iter().map(|x| x.to_string()).collect();
+        write!(f, "seeds: {}", seeds.join(" "))?;
+
+        for section in &self.maps {
+            write!(f, "\n\n{}-to-{} map:", section.source, section.destination)?;
+            for map_range in &section.entries {
+                write!(
+                    f,
+                    "\n{} {} {}",
+                    map_range.destination_range_start,
+                    map_range.source_range_start,
+                    map_range.range_length
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl std::str::FromStr for Almanac {
-    type Err = Box<dyn Error>;
+    type Err = AlmanacError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         let mut tokens = value.split("\n\n");
 
-        let seeds: Vec<u64> = tokens
-            .next()
-            .ok_or("Missing seeds")?
+        let seeds_line = tokens.next().unwrap_or(value);
+        let seeds: Vec<u64> = seeds_line
             .strip_prefix("seeds: ")
-            .ok_or("Missing seeds prefix")?
+            .ok_or_else(|| {
+                ParseError::new(
+                    AlmanacErrorKind::MissingSeedsPrefix,
+                    Location::find(value, seeds_line),
+                    seeds_line,
+                )
+            })?
             .split(' ')
-            .map(|x| x.parse())
+            .map(|x| {
+                x.parse().map_err(|_| {
+                    ParseError::new(AlmanacErrorKind::InvalidNumber, Location::find(value, x), x)
+                })
+            })
             .collect::<Result<Vec<u64>, _>>()?;
 
-        let mut maps: HashMap<String, Vec<AlmanacMap>> = HashMap::new();
+        let mut maps: Vec<MapSection> = vec![];
         for map_section in tokens {
-            let (name, map) = get_map(map_section)?;
-            maps.insert(name, map);
+            let section = get_map(value, map_section)?;
+            let existing = maps
+                .iter_mut()
+                .find(|x| x.source == section.source && x.destination == section.destination);
+            match existing {
+                Some(existing) => *existing = section,
+                None => maps.push(section),
+            }
         }
 
-        Ok(Almanac { seeds, maps })
+        Ok(Almanac::new(seeds, maps))
     }
 }
@@ -0,0 +1,669 @@
+pub mod almanac;
+
+use almanac::Almanac;
+use common::Solution;
+use std::error::Error;
+use std::ops::Range;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Almanac;
+    type Output = u64;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        get_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        get_part2(input)
+    }
+}
+
+/// A seed and the location it ends up at.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SeedReport {
+    pub seed: u64,
+    pub location: u64,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AlmanacReport<'a> {
+    pub almanac: &'a Almanac,
+    pub seeds: Vec<SeedReport>,
+    /// Location ranges when the seeds are read as (start, length) pairs, or
+    /// `None` when there is an odd number of seeds.
+    pub seed_range_locations: Option<Vec<Range<u64>>>,
+}
+
+pub fn report(almanac: &Almanac) -> Result<AlmanacReport<'_>, Box<dyn Error>> {
+    let seeds = almanac
+        .seeds()
+        .iter()
+        .zip(almanac.map_all_seeds_to_location()?)
+        .map(|(seed, location)| SeedReport {
+            seed: *seed,
+            location,
+        })
+        .collect();
+
+    Ok(AlmanacReport {
+        almanac,
+        seeds,
+        seed_range_locations: almanac.map_seed_ranges_to_location().ok(),
+    })
+}
+
+fn get_part1(almanac: &Almanac) -> Result<u64, Box<dyn Error>> {
+    Ok(almanac
+        .map_all_seeds_to_location()?
+        .into_iter()
+        .min()
+        .ok_or("no data")?)
+}
+
+fn get_part2(almanac: &Almanac) -> Result<u64, Box<dyn Error>> {
+    Ok(almanac
+        .map_seed_ranges_to_location()?
+        .into_iter()
+        .map(|x| x.start)
+        .min()
+        .ok_or("no data")?)
+}
+
+/// Works out part 2 backwards: the lowest location is the smallest `n` for
+/// which some seed range reaches `0..n + 1`, found by binary search.
+pub fn get_part2_reverse(almanac: &Almanac) -> Result<u64, Box<dyn Error>> {
+    let seed_ranges = almanac.seed_ranges()?;
+    let chain = almanac.chain("seed", "location")?;
+    let reaches = |end: u64| {
+        let locations = 0..end;
+        let seeds = chain.unmap_ranges(vec![locations]);
+        seeds.iter().any(|x| {
+            seed_ranges
+                .iter()
+                .any(|seed| x.start.max(seed.start) < x.end.min(seed.end))
+        })
+    };
+
+    if !reaches(u64::MAX) {
+        return Err("no data".into());
+    }
+    let (mut low, mut high) = (0, u64::MAX);
+    while low + 1 < high {
+        let middle = low + (high - low) / 2;
+        if reaches(middle) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    Ok(high - 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use almanac::{AlmanacError, AlmanacErrorKind, ChainError};
+    use common::rng::Rng;
+    use common::{Location, ParseError};
+
+    #[test]
+    fn test_part1_example() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "seeds: 79 14 55 13",
+            "",
+            "seed-to-soil map:",
+            "50 98 2",
+            "52 50 48",
+            "",
+            "soil-to-fertilizer map:",
+            "0 15 37",
+            "37 52 2",
+            "39 0 15",
+            "",
+            "fertilizer-to-water map:",
+            "49 53 8",
+            "0 11 42",
+            "42 0 7",
+            "57 7 4",
+            "",
+            "water-to-light map:",
+            "88 18 7",
+            "18 25 70",
+            "",
+            "light-to-temperature map:",
+            "45 77 23",
+            "81 45 19",
+            "68 64 13",
+            "",
+            "temperature-to-humidity map:",
+            "0 69 1",
+            "1 0 69",
+            "",
+            "humidity-to-location map:",
+            "60 56 37",
+            "56 93 4",
+        ]
+        .join("\n");
+
+        let almanac: Almanac = example.parse()?;
+        assert_eq!(almanac.to_string(), example);
+        let locations = almanac.map_all_seeds_to_location()?;
+        assert_eq!(locations, [82, 43, 86, 35,]);
+        let result = get_part1(&almanac)?;
+
+        assert_eq!(result, 35);
+
+        let result = get_part2(&almanac)?;
+        assert_eq!(result, 46);
+        assert_eq!(get_part2_reverse(&almanac)?, 46);
+
+        let report = report(&almanac)?;
+        assert_eq!(
+            report.seeds[1],
+            SeedReport {
+                seed: 14,
+                location: 43
+            }
+        );
+        let ranges = report.seed_range_locations.ok_or("missing seed ranges")?;
+        assert_eq!(ranges.iter().map(|x| x.start).min(), Some(46));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2_large_ranges() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "seeds: 1000000000 3000000000",
+            "",
+            "seed-to-soil map:",
+            "5 2000000000 1000000000",
+            "",
+            "soil-to-fertilizer map:",
+            "",
+            "fertilizer-to-water map:",
+            "",
+            "water-to-light map:",
+            "",
+            "light-to-temperature map:",
+            "",
+            "temperature-to-humidity map:",
+            "",
+            "humidity-to-location map:",
+        ]
+        .join("\n");
+
+        let almanac: Almanac = example.parse()?;
+        let result = get_part2(&almanac)?;
+        assert_eq!(result, 5);
+        assert_eq!(get_part2_reverse(&almanac)?, 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_mapping() -> Result<(), Box<dyn Error>> {
+        let steps = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ];
+        let mut rng = Rng::new(24);
+
+        for _ in 0..200 {
+            let mut sections = vec!["seeds: 0 1".to_string()];
+            for step in steps {
+                let mut lines = vec![format!("{} map:", step)];
+                for _ in 0..rng.range(0, 4) {
+                    let values: Vec<String> = [40, 40, 12]
+                        .iter()
+                        .map(|x| rng.range(0, *x).to_string())
+                        .collect();
+                    lines.push(values.join(" "));
+                }
+                sections.push(lines.join("\n"));
+            }
+            let example = sections.join("\n\n");
+            let almanac: Almanac = example.parse()?;
+
+            // Brute force the inverse over every seed that can reach 0..60.
+            let forward: Vec<(u64, u64)> = (0..200)
+                .map(|seed| Ok((seed, almanac.map_seed_to_location(seed)?)))
+                .collect::<Result<_, Box<dyn Error>>>()?;
+            for location in 0..60 {
+                let expected: Vec<u64> = forward
+                    .iter()
+                    .filter(|(_, x)| *x == location)
+                    .map(|(seed, _)| *seed)
+                    .collect();
+                let seeds = almanac.map_location_to_seed(location)?;
+                assert_eq!(seeds, expected, "{} {}", location, example);
+            }
+
+            let seeds = almanac.map_location_range_to_seed(10..30)?;
+            for (seed, location) in &forward {
+                let inside = seeds.iter().any(|x| x.contains(seed));
+                assert_eq!(inside, (10..30).contains(location), "{} {}", seed, example);
+            }
+
+            assert_eq!(almanac.map_location_to_seed(u64::MAX)?, [u64::MAX]);
+        }
+
+        let example = [
+            "seeds: 18446744073709551615",
+            "seed-to-location map:\n0 5 1",
+        ]
+        .join("\n\n");
+        let almanac: Almanac = example.parse()?;
+        assert_eq!(almanac.map_seed_to_location(u64::MAX)?, u64::MAX);
+        assert_eq!(almanac.map_location_to_seed(u64::MAX)?, [u64::MAX]);
+        assert_eq!(almanac.map_location_to_seed(0)?, [0, 5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chain_from_headers() -> Result<(), Box<dyn Error>> {
+        // Sections out of order, with an extra stage between soil and
+        // location that no hardcoded list would know about.
+        let example = [
+            "seeds: 1 10",
+            "",
+            "compost-to-location map:",
+            "100 0 50",
+            "",
+            "seed-to-soil map:",
+            "20 0 5",
+            "",
+            "soil-to-compost map:",
+            "0 20 3",
+        ]
+        .join("\n");
+        let almanac: Almanac = example.parse()?;
+
+        assert_eq!(
+            almanac.path("seed", "location")?,
+            ["seed", "soil", "compost", "location"]
+        );
+        assert_eq!(almanac.map_seed_to_location(1)?, 101);
+        assert_eq!(almanac.map_seed_to_location(10)?, 110);
+        let chain = almanac.chain("seed", "location")?;
+        assert_eq!(chain.map_value(1), 101);
+        assert_eq!(
+            chain.unmap_ranges(vec![101..103, 200..201]),
+            [1..3, 21..23, 101..103, 200..201]
+        );
+        assert_eq!(almanac.map_value("soil", "compost", 22)?, 2);
+        assert_eq!(almanac.map_value("soil", "soil", 22)?, 22);
+        assert_eq!(get_part1(&almanac)?, 101);
+        assert_eq!(get_part2(&almanac)?, 101);
+        assert_eq!(get_part2_reverse(&almanac)?, 101);
+        assert_eq!(
+            almanac.map_ranges("compost", "location", vec![40..60, 70..71])?,
+            [140..150, 50..60, 70..71]
+        );
+        assert_eq!(
+            almanac.unmap_ranges("soil", "location", vec![100..102, 200..201])?,
+            [0..2, 20..22, 100..102, 200..201]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chain_errors() -> Result<(), Box<dyn Error>> {
+        let almanac = |headers: &[&str]| -> Result<Almanac, AlmanacError> {
+            let sections: Vec<String> = headers.iter().map(|x| format!("{} map:", x)).collect();
+            format!("seeds: 1\n\n{}", sections.join("\n\n")).parse()
+        };
+        let names = |values: &[&str]| values.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+        let cyclic = almanac(&[
+            "seed-to-soil",
+            "soil-to-water",
+            "water-to-soil",
+            "light-to-location",
+        ])?;
+        assert_eq!(
+            cyclic.path("seed", "location").unwrap_err(),
+            ChainError::Cycle(names(&["soil", "water", "soil"]))
+        );
+
+        // Cycles that only touch the path are fine, wherever their sections
+        // appear.
+        let off_path = [
+            ["soil-to-x", "x-to-soil", "seed-to-soil", "soil-to-location"],
+            ["seed-to-soil", "soil-to-location", "soil-to-x", "x-to-soil"],
+        ];
+        for headers in off_path {
+            assert_eq!(
+                almanac(&headers)?.path("seed", "location")?,
+                ["seed", "soil", "location"],
+                "{:?}",
+                headers
+            );
+        }
+
+        // A cycle around a step of the path is not, since values could go
+        // around it any number of times.
+        let on_path = [
+            [
+                "seed-to-soil",
+                "soil-to-water",
+                "water-to-soil",
+                "water-to-location",
+            ],
+            [
+                "water-to-soil",
+                "water-to-location",
+                "soil-to-water",
+                "seed-to-soil",
+            ],
+        ];
+        for headers in on_path {
+            assert_eq!(
+                almanac(&headers)?.path("seed", "location").unwrap_err(),
+                ChainError::Cycle(names(&["soil", "water", "soil"])),
+                "{:?}",
+                headers
+            );
+        }
+
+        let dead_end = almanac(&["seed-to-soil", "soil-to-water", "light-to-location"])?;
+        let error = dead_end.path("seed", "location").unwrap_err();
+        assert_eq!(
+            error,
+            ChainError::DeadEnd("water".to_string(), "location".to_string())
+        );
+        assert_eq!(
+            error.to_string(),
+            "no map out of \"water\" on the way to \"location\""
+        );
+        assert_eq!(
+            dead_end.map_seed_to_location(1).unwrap_err().to_string(),
+            error.to_string()
+        );
+
+        let ambiguous = almanac(&[
+            "seed-to-soil",
+            "seed-to-water",
+            "soil-to-location",
+            "water-to-location",
+        ])?;
+        let error = ambiguous.path("seed", "location").unwrap_err();
+        assert_eq!(
+            error,
+            ChainError::Ambiguous(
+                names(&["seed", "soil", "location"]),
+                names(&["seed", "water", "location"])
+            )
+        );
+        assert_eq!(
+            error.to_string(),
+            "ambiguous paths: seed -> soil -> location and seed -> water -> location"
+        );
+        assert_eq!(ambiguous.path("soil", "location")?, ["soil", "location"]);
+
+        // A cycle does not hide a second path, whichever is found first.
+        for headers in [
+            [
+                "seed-to-soil",
+                "soil-to-seed",
+                "soil-to-location",
+                "seed-to-location",
+            ],
+            [
+                "seed-to-location",
+                "soil-to-location",
+                "soil-to-seed",
+                "seed-to-soil",
+            ],
+        ] {
+            let error = almanac(&headers)?.path("seed", "location").unwrap_err();
+            assert!(matches!(error, ChainError::Ambiguous(..)), "{:?}", headers);
+        }
+
+        assert_eq!(
+            ambiguous.path("seed", "humidity").unwrap_err(),
+            ChainError::UnknownCategory("humidity".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "seed: 1 2".parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                AlmanacErrorKind::MissingSeedsPrefix,
+                Location { line: 1, column: 1 },
+                "seed: 1 2"
+            )
+        );
+
+        let example = ["seeds: 1 2", "", "seed-to-soil map:", "50 98 2", "52 5x 48"].join("\n");
+        let error = example.parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                AlmanacErrorKind::InvalidNumber,
+                Location { line: 5, column: 4 },
+                "5x"
+            )
+        );
+        assert_eq!(error.to_string(), "line 5, column 4: invalid number \"5x\"");
+
+        let example = ["seeds: 1 2", "", "seed-to-soil", "50 98 2"].join("\n");
+        let error = example.parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                AlmanacErrorKind::MissingMapSuffix,
+                Location { line: 3, column: 1 },
+                "seed-to-soil"
+            )
+        );
+
+        let example = ["seeds: 1 2", "", "seed-soil map:", "50 98 2"].join("\n");
+        let error = example.parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                AlmanacErrorKind::InvalidMapName,
+                Location { line: 3, column: 1 },
+                "seed-soil"
+            )
+        );
+
+        let example = ["seeds: 1 2", "", "seed-to-soil map:", "50 98"].join("\n");
+        let error = example.parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                AlmanacErrorKind::MissingRangeLength,
+                Location { line: 4, column: 1 },
+                "50 98"
+            )
+        );
+
+        let entry = "0 18446744073709551615 2";
+        let example = ["seeds: 1 2", "", "seed-to-soil map:", entry].join("\n");
+        let error = example.parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                AlmanacErrorKind::RangeOverflow,
+                Location { line: 4, column: 1 },
+                entry
+            )
+        );
+
+        let entry = "18446744073709551615 0 1";
+        let example = ["seeds: 1 2", "", "seed-to-soil map:", entry].join("\n");
+        let error = example.parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                AlmanacErrorKind::RangeOverflow,
+                Location { line: 4, column: 1 },
+                entry
+            )
+        );
+    }
+
+    #[test]
+    fn test_seed_range_overflow() -> Result<(), Box<dyn Error>> {
+        let almanac: Almanac = "seeds: 18446744073709551615 1".parse()?;
+        assert!(almanac.seed_ranges().is_err());
+        assert!(get_part2(&almanac).is_err());
+
+        let almanac: Almanac = "seeds: 3 4 18446744073709551614 1".parse()?;
+        assert_eq!(almanac.seed_ranges()?, [3..7, u64::MAX - 1..u64::MAX]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_round_trip() -> Result<(), Box<dyn Error>> {
+        let names = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "water-to-light",
+            "x-to-y",
+        ];
+        let mut rng = Rng::new(5);
+
+        for _ in 0..300 {
+            let seeds: Vec<String> = (0..rng.range(1, 6))
+                .map(|_| rng.range(0, 1 << 32).to_string())
+                .collect();
+            let mut sections = vec![format!("seeds: {}", seeds.join(" "))];
+            for _ in 0..rng.range(0, 5) {
+                let mut lines = vec![format!("{} map:", rng.choose(&names))];
+                for _ in 0..rng.range(0, 4) {
+                    let values: Vec<String> =
+                        (0..3).map(|_| rng.range(0, 100).to_string()).collect();
+                    lines.push(values.join(" "));
+                }
+                sections.push(lines.join("\n"));
+            }
+            let example = sections.join("\n\n");
+
+            let almanac: Almanac = example.parse()?;
+            let canonical = almanac.to_string();
+            let reparsed: Almanac = canonical.parse()?;
+            assert_eq!(reparsed, almanac, "{}", example);
+            assert_eq!(reparsed.to_string(), canonical);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_report_json() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "seeds: 3 2 10",
+            "",
+            "seed-to-soil map:",
+            "20 3 1",
+            "",
+            "soil-to-location map:",
+            "0 20 1",
+        ]
+        .join("\n");
+        let almanac: Almanac = example.parse()?;
+        let json = serde_json::to_value(report(&almanac)?)?;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "almanac": {
+                    "seeds": [3, 2, 10],
+                    "maps": [
+                        {
+                            "source": "seed",
+                            "destination": "soil",
+                            "entries": [{
+                                "destination_range_start": 20,
+                                "source_range_start": 3,
+                                "range_length": 1,
+                            }],
+                        },
+                        {
+                            "source": "soil",
+                            "destination": "location",
+                            "entries": [{
+                                "destination_range_start": 0,
+                                "source_range_start": 20,
+                                "range_length": 1,
+                            }],
+                        },
+                    ],
+                },
+                "seeds": [
+                    { "seed": 3, "location": 0 },
+                    { "seed": 2, "location": 2 },
+                    { "seed": 10, "location": 10 },
+                ],
+                "seed_range_locations": null,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_json_round_trip() -> Result<(), Box<dyn Error>> {
+        let example = [
+            "seeds: 79 14 55 13",
+            "",
+            "seed-to-soil map:",
+            "50 98 2",
+            "52 50 48",
+            "",
+            "soil-to-location map:",
+            "0 15 37",
+        ]
+        .join("\n");
+        let almanac: Almanac = example.parse()?;
+        let json = serde_json::to_string(&almanac)?;
+        let imported: Almanac = serde_json::from_str(&json)?;
+        assert_eq!(imported, almanac);
+        assert_eq!(imported.map_seed_to_location(79)?, 81);
+
+        // Imported entries are held to the same bounds as parsed ones.
+        let json = r#"{
+            "seeds": [1],
+            "maps": [{
+                "source": "seed",
+                "destination": "location",
+                "entries": [{
+                    "destination_range_start": 0,
+                    "source_range_start": 18446744073709551615,
+                    "range_length": 1
+                }]
+            }]
+        }"#;
+        let error = serde_json::from_str::<Almanac>(json).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("seed-to-location entry 0 18446744073709551615 1 runs past"),
+            "{}",
+            error
+        );
+
+        Ok(())
+    }
+}